@@ -0,0 +1,16 @@
+#![allow(non_snake_case)]
+
+pub mod simplex;
+
+pub use simplex::{tableau, fraction, scalar, big_m, solution, error, options, model, sensitivity, pricing, ratio_test};
+pub use simplex::tableau::Tableau;
+pub use simplex::fraction::{Fraction, ExtendedFraction};
+pub use simplex::scalar::Scalar;
+pub use simplex::big_m::BigM;
+pub use simplex::solution::{Solution, SolveStatus, SolveStatistics};
+pub use simplex::error::SimplexError;
+pub use simplex::options::{SolverOptions, SolveType, VariableSelectType, RatioTestType, BigMSolveType, ObjectiveSense};
+pub use simplex::model::{Model, Variable, LinearExpression, ConstraintType, ModelSolution};
+pub use simplex::sensitivity::{SensitivityReport, SensitivityRange};
+pub use simplex::pricing::{PricingRule, PricingContext, Bland, Dantzig, CustomPricingRule};
+pub use simplex::ratio_test::{RatioTestRule, RatioTestContext, RatioCandidate, LowestRow, LowestBasicIndex, LargestPivot, Lexicographic, CustomRatioTestRule};
@@ -1,13 +1,30 @@
-mod simplex;
-use simplex::tableau::Tableau;
+#![allow(non_snake_case)]
+
+use simplex::{Tableau, SolverOptions, SolveType, VariableSelectType, BigMSolveType};
 
 fn main() {
-    let A = vec![vec![0.25f64,0.5f64,0f64], vec![-8f64,-0.5f64,0f64], vec![-1f64,-0.5f64,1f64], vec![9f64,3f64,0f64], vec![1f64,0f64,0f64], vec![0f64,1f64,0f64], vec![0f64,0f64,1f64]]; 
+    let A = [vec![0.25f64,0.5f64,0f64], vec![-8f64,-0.5f64,0f64], vec![-1f64,-0.5f64,1f64], vec![9f64,3f64,0f64], vec![1f64,0f64,0f64], vec![0f64,1f64,0f64], vec![0f64,0f64,1f64]]; 
+    let A: Vec<&[f64]> = A.iter().map(|col| col.as_slice()).collect();
     let b = vec![0f64,0f64,1f64];
     let c = vec![0.75f64,-20f64,0.5f64,-6f64,0f64,0f64,0f64];
-    let mut tableau = Tableau::new(&A,&b,&c,String::from("standard"),String::from("bland"),String::from("twophase"));
-    tableau.set_debug(true);
-    tableau.solve();
+    let options = SolverOptions::new()
+        .solve_type(SolveType::Standard)
+        .variable_select_type(VariableSelectType::Bland)
+        .big_M_solve_type(BigMSolveType::TwoPhase)
+        .debug(true);
+    let mut tableau = match Tableau::new(&A,&b,&c,&options) {
+        Ok(tableau) => tableau,
+        Err(e) => {
+            println!("{}", e);
+            return;
+        },
+    };
+    if let Err(e) = tableau.solve() {
+        println!("{}", e);
+        return;
+    }
     tableau.find_b_inverse();
+    if let Some(report) = tableau.sensitivity() {
+        report.print_table();
+    }
 }
-
@@ -1,2 +1,11 @@
 pub mod tableau;
-pub mod fraction;
\ No newline at end of file
+pub mod fraction;
+pub mod scalar;
+pub mod big_m;
+pub mod solution;
+pub mod error;
+pub mod options;
+pub mod model;
+pub mod sensitivity;
+pub mod pricing;
+pub mod ratio_test;
\ No newline at end of file
@@ -0,0 +1,208 @@
+use std::{fmt,ops,cmp};
+use super::fraction::Fraction;
+use super::scalar::Scalar;
+
+// A number a + b*M, where M is larger than any number of the Tableau. This is the cost of an artificial variable in the Big M method,
+// and the reduced costs and objective value that come from it.
+// Numbers are ordered by the coefficient of M first, and by the constant part when those are equal
+#[derive(Debug, Clone, PartialEq)]
+pub struct BigM<T: Scalar = Fraction> {
+    pub constant: T,
+    pub big_M: T,
+}
+
+impl<T: Scalar> BigM<T> {
+    pub fn new(constant: T, big_M: T) -> BigM<T> {
+        BigM {
+            constant,
+            big_M,
+        }
+    }
+
+    pub fn zero() -> BigM<T> {
+        BigM::new(T::zero(), T::zero())
+    }
+
+    // M itself
+    pub fn M() -> BigM<T> {
+        BigM::new(T::zero(), T::one())
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.sign(T::tolerance()) == 0
+    }
+
+    // whether the number has a part that multiplies M
+    pub fn is_big(&self) -> bool {
+        self.big_M.sign(T::tolerance()) != 0
+    }
+
+    pub fn abs(&self) -> Self {
+        if self.sign(T::tolerance()) < 0 {
+            -self.clone()
+        } else {
+            self.clone()
+        }
+    }
+
+    // the same number with any part that is within tolerance of 0 set to 0, so that rounding errors in the coefficient of M do not outweigh the constant part
+    pub fn snap(&self, tolerance: f64) -> BigM<T> {
+        let snap = |value: &T| if value.sign(tolerance) == 0 { T::zero() } else { value.clone() };
+        BigM::new(snap(&self.constant), snap(&self.big_M))
+    }
+
+    // the sign of the coefficient of M, or of the constant part when there is no M
+    pub fn sign(&self, tolerance: f64) -> i64 {
+        match self.big_M.sign(tolerance) {
+            0 => self.constant.sign(tolerance),
+            sign => sign,
+        }
+    }
+}
+
+impl<T: Scalar> fmt::Display for BigM<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let coefficient = self.big_M.abs();
+        let big_M_string = if coefficient == T::one() {
+            String::from("M")
+        } else {
+            format!("{}M", coefficient)
+        };
+        let big_M_sign = self.big_M.sign(T::tolerance());
+        if big_M_sign == 0 {
+            write!(f, "{}", self.constant)
+        } else if self.constant.sign(T::tolerance()) == 0 && big_M_sign < 0 {
+            write!(f, "-{}", big_M_string)
+        } else if self.constant.sign(T::tolerance()) == 0 {
+            write!(f, "{}", big_M_string)
+        } else if big_M_sign < 0 {
+            write!(f, "{} - {}", self.constant, big_M_string)
+        } else {
+            write!(f, "{} + {}", self.constant, big_M_string)
+        }
+    }
+}
+
+impl<T: Scalar> ops::Add for BigM<T> {
+    type Output = BigM<T>;
+
+    fn add(self, other: BigM<T>) -> BigM<T> {
+        BigM::new(self.constant + other.constant, self.big_M + other.big_M)
+    }
+}
+
+impl<T: Scalar> ops::Sub for BigM<T> {
+    type Output = BigM<T>;
+
+    fn sub(self, other: BigM<T>) -> BigM<T> {
+        BigM::new(self.constant - other.constant, self.big_M - other.big_M)
+    }
+}
+
+// M * M is not a BigM, so they are only multiplied and divided by numbers without M
+impl<T: Scalar> ops::Mul<T> for BigM<T> {
+    type Output = BigM<T>;
+
+    fn mul(self, other: T) -> BigM<T> {
+        BigM::new(self.constant * other.clone(), self.big_M * other)
+    }
+}
+
+impl<T: Scalar> ops::Div<T> for BigM<T> {
+    type Output = BigM<T>;
+
+    fn div(self, other: T) -> BigM<T> {
+        BigM::new(self.constant / other.clone(), self.big_M / other)
+    }
+}
+
+impl<T: Scalar> ops::Neg for BigM<T> {
+    type Output = BigM<T>;
+
+    fn neg(self) -> BigM<T> {
+        BigM::new(-self.constant, -self.big_M)
+    }
+}
+
+impl<T: Scalar> PartialOrd for BigM<T> {
+    fn partial_cmp(&self, other: &BigM<T>) -> Option<cmp::Ordering> {
+        match self.big_M.partial_cmp(&other.big_M) {
+            Some(cmp::Ordering::Equal) => self.constant.partial_cmp(&other.constant),
+            ordering => ordering,
+        }
+    }
+}
+
+impl<T: Scalar> From<T> for BigM<T> {
+    fn from(constant: T) -> BigM<T> {
+        BigM::new(constant, T::zero())
+    }
+}
+
+impl From<i64> for BigM {
+    fn from(i: i64) -> BigM {
+        BigM::from(Fraction::from(i))
+    }
+}
+
+#[cfg(test)]
+mod big_m_test {
+    use super::BigM;
+    use super::super::fraction::Fraction;
+    fn big_M(constant: i64, big_M: i64) -> BigM {
+        BigM::new(Fraction::from(constant), Fraction::from(big_M))
+    }
+    #[test]
+    fn arithmetic() {
+        assert_eq!(big_M(5, -1), big_M(3, 1) + big_M(2, -2));
+        assert_eq!(big_M(1, 3), big_M(3, 1) - big_M(2, -2));
+        assert_eq!(big_M(-3, -1), -big_M(3, 1));
+        assert_eq!(big_M(6, -4), big_M(3, -2) * Fraction::from(2));
+        assert_eq!(BigM::new(Fraction::new(3,2), Fraction::from(-1)), big_M(3, -2) / Fraction::from(2));
+        assert_eq!(big_M(0, 0), big_M(3, -2) * Fraction::from(0));
+        assert!(big_M(0, 0).is_zero());
+        assert!(!big_M(0, 1).is_zero());
+        assert!(big_M(1, -1).is_big());
+        assert!(!BigM::from(7).is_big());
+    }
+    #[test]
+    fn comparisons() {
+        // any amount of M outweighs the constant part
+        assert!(BigM::M() > BigM::from(i64::MAX));
+        assert!(big_M(-1000, 1) > big_M(1000, 0));
+        assert!(BigM::new(Fraction::from(0), Fraction::new(-1,1000)) < BigM::from(-1000));
+        assert!(big_M(2, 1) > big_M(1, 1));
+        assert!(big_M(2, -1) < big_M(1, 0));
+        assert!(BigM::from(3) == BigM::from(Fraction::from(3)));
+        assert_eq!(big_M(3, 2), big_M(-3, -2).abs());
+        assert_eq!(big_M(-3, 2), big_M(-3, 2).abs());
+    }
+    #[test]
+    fn display() {
+        assert_eq!("3 - 2M", format!("{}", big_M(3, -2)));
+        assert_eq!("3 + M", format!("{}", big_M(3, 1)));
+        assert_eq!("-1/2 + 3M", format!("{}", BigM::new(Fraction::new(-1,2), Fraction::from(3))));
+        assert_eq!("M", format!("{}", BigM::<Fraction>::M()));
+        assert_eq!("-M", format!("{}", -BigM::<Fraction>::M()));
+        assert_eq!("-2M", format!("{}", big_M(0, -2)));
+        assert_eq!("7", format!("{}", BigM::from(7)));
+        assert_eq!("0", format!("{}", big_M(0, 0)));
+        assert_eq!("1.5 - 2M", format!("{}", BigM::new(1.5f64, -2f64)));
+    }
+    #[test]
+    fn tolerance() {
+        // a coefficient of M that is only rounding error does not outweigh the constant part
+        let rounding = BigM::new(-1f64, 0.1f64 + 0.2f64 - 0.3f64);
+        assert_eq!(-1, rounding.sign(1e-9));
+        assert!(!rounding.is_big());
+        assert_eq!("-1", format!("{}", rounding));
+        assert!(BigM::new(0f64, 1e-12).is_zero());
+        // once the rounding error is snapped away the numbers are ordered by their constant parts
+        assert!(rounding > BigM::zero());
+        assert!(rounding.snap(1e-9) < BigM::zero());
+        assert!(-BigM::new(-5f64, 1e-17) < BigM::zero());
+        assert!(-BigM::new(-5f64, 1e-17).snap(1e-9) > BigM::zero());
+        assert_eq!(BigM::new(-5f64, 0f64), BigM::new(-5f64, 1e-17).snap(1e-9));
+        assert_eq!(big_M(0, 1), big_M(0, 1).snap(1e-9));
+    }
+}
@@ -0,0 +1,59 @@
+use std::{error,fmt};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SimplexError {
+    EmptyProblem,
+    RaggedMatrix { column: usize, rows: usize, expected_rows: usize },
+    CostDimensionMismatch { columns: usize, costs: usize },
+    RightHandSideDimensionMismatch { rows: usize, right_hand_sides: usize },
+    UnrepresentableValue(f64),
+    ZeroDenominator,
+    IncompleteSolve,
+    UnknownVariable(usize),
+    BoundsDimensionMismatch { columns: usize, bounds: usize },
+    InvalidBounds { column: usize },
+    UnsupportedBounds { column: usize },
+    UnsupportedArtificialVariable { column: usize },
+    UnsupportedRule,
+    InvalidTolerance(f64),
+    InvalidEnteringVariable { column: usize },
+    InvalidLeavingRow { row: usize },
+}
+
+// turns a 0 based index into a 1 based position like "1st", "2nd", "3rd" or "11th"
+fn ordinal(i: usize) -> String {
+    let position = i+1;
+    let suffix = match (position%10, position%100) {
+        (_, 11) | (_, 12) | (_, 13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{}{}", position, suffix)
+}
+
+impl fmt::Display for SimplexError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SimplexError::EmptyProblem => write!(f, "A must have at least one row and one column."),
+            SimplexError::RaggedMatrix { column, rows, expected_rows } => write!(f, "The {} column of A has {} rows, but the first column has {}.", ordinal(*column), rows, expected_rows),
+            SimplexError::CostDimensionMismatch { columns, costs } => write!(f, "A and c matrices are not compatable. c is 1x{} and A has {} columns.", costs, columns),
+            SimplexError::RightHandSideDimensionMismatch { rows, right_hand_sides } => write!(f, "A and b matrices are not compatable. A has {} rows and b is {}x1.", rows, right_hand_sides),
+            SimplexError::UnrepresentableValue(value) => write!(f, "{} is not a finite number, so it cannot be represented as a fraction.", value),
+            SimplexError::ZeroDenominator => write!(f, "Cannot create a fraction with a denominator of 0."),
+            SimplexError::UnknownVariable(index) => write!(f, "The variable with index {} does not belong to this model.", index),
+            SimplexError::BoundsDimensionMismatch { columns, bounds } => write!(f, "A has {} columns, but {} bounds were given.", columns, bounds),
+            SimplexError::InvalidBounds { column } => write!(f, "The bounds of the {} variable are invalid. The lower bound must not be greater than the upper bound.", ordinal(*column)),
+            SimplexError::UnsupportedBounds { column } => write!(f, "The bounds of the {} variable are not supported by this solve type.", ordinal(*column)),
+            SimplexError::UnsupportedArtificialVariable { column } => write!(f, "The {} variable is artificial, which is not supported by this solve type.", ordinal(*column)),
+            SimplexError::UnsupportedRule => write!(f, "The custom pricing or ratio test rule was written for a different Scalar than the tableau."),
+            SimplexError::InvalidEnteringVariable { column } => write!(f, "The pricing rule chose the {} variable, which cannot improve the objective.", ordinal(*column)),
+            SimplexError::InvalidLeavingRow { row } => write!(f, "The ratio test rule chose the {} row, which does not tie for the minimum ratio.", ordinal(*row)),
+            SimplexError::InvalidTolerance(tolerance) => write!(f, "{} is not a valid tolerance. Tolerances must be finite and not negative.", tolerance),
+            SimplexError::IncompleteSolve => write!(f, "The tableau stopped before it was found to be optimal, unbounded or infeasible."),
+        }
+    }
+}
+
+impl error::Error for SimplexError {}
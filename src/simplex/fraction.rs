@@ -1,70 +1,70 @@
 use std::{fmt,ops,cmp};
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{Signed, ToPrimitive, Zero};
+use super::error::SimplexError;
+use super::scalar::Scalar;
 
-fn gcd(a: i64, b:i64) -> i64 {
-    // Euclidean algorithm
-    if b == 0{
-        if a >= 0 {
-            a
-        }
-        else {
-            -a
-        }
-    }
-    else {
-        gcd(b, a%b)
-    }
-}
-
+// An exact fraction, which is a BigRational and so is always kept reduced with a positive denominator.
+// The numerator and denominator grow as large as they need to, so pivoting never overflows
 #[derive(Debug, Clone, PartialEq)]
-pub struct Fraction {
-    pub numerator: i64,
-    pub denominator: i64,
-}
+pub struct Fraction(BigRational);
 
 impl Fraction {
     pub fn new(n: i64, d: i64) -> Fraction {
         assert_ne!(d,0, "Cannot create a fraction with a denominator of 0.");
-        let mut res = Fraction {
-            numerator: n,
-            denominator: d,
-        };
-        // we don't want to be reducing our fractions if we are dealing with an infinite value.
-        if n.abs() != i64::MAX && d.abs() != i64::MAX {
-            res.reduce();
-            res
-        } else {
-            res
-        }
+        Fraction(BigRational::new(BigInt::from(n), BigInt::from(d)))
     }
 
-    pub fn abs(&self) -> Self {
-        if self.numerator < 0i64 {
-            Fraction {
-                numerator: -self.numerator,
-                denominator: self.denominator,
-            }
+    // same as new, but returns an error instead of panicking on a denominator of 0
+    pub fn try_new(n: i64, d: i64) -> Result<Fraction, SimplexError> {
+        if d == 0 {
+            Err(SimplexError::ZeroDenominator)
         } else {
-            self.clone()
+            Ok(Fraction::new(n, d))
         }
     }
 
-    fn reduce(&mut self){
-        let divisor = gcd(self.numerator, self.denominator);
-        self.numerator /= divisor;
-        self.denominator /= divisor;
-        if self.denominator < 0 {
-            self.numerator *= -1;
-            self.denominator *= -1;
+    // same as Fraction::from(f64), but returns an error for NaN and infinity instead of panicking.
+    // Every finite f64 is a fraction with a power of 2 as its denominator, so it is read in exactly, however large or small it is
+    pub fn try_from_f64(f: f64) -> Result<Fraction, SimplexError> {
+        match BigRational::from_float(f) {
+            Some(value) => Ok(Fraction(value)),
+            None => Err(SimplexError::UnrepresentableValue(f)),
         }
     }
+
+    pub fn numerator(&self) -> &BigInt {
+        self.0.numer()
+    }
+
+    pub fn denominator(&self) -> &BigInt {
+        self.0.denom()
+    }
+
+    pub fn abs(&self) -> Self {
+        Fraction(Signed::abs(&self.0))
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+
+    pub fn is_positive(&self) -> bool {
+        self.0.is_positive()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.0.is_negative()
+    }
 }
 
 impl fmt::Display for Fraction {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        if self.denominator == 1 {
-            write!(f, "{}", self.numerator)
+        if self.0.is_integer() {
+            write!(f, "{}", self.numerator())
         } else {
-            write!(f, "{}/{}", self.numerator, self.denominator)
+            write!(f, "{}/{}", self.numerator(), self.denominator())
         }
     }
 }
@@ -73,51 +73,7 @@ impl ops::Sub for Fraction {
     type Output = Fraction;
 
     fn sub(self, other: Fraction) -> Fraction {
-        // INF - INF = 0
-        if self.numerator.abs() == i64::MAX && other.numerator == self.numerator {
-            if self.numerator > 0 {
-        // INF - INF
-                if other.numerator > 0 {
-                    Fraction::from(0)
-                } else {
-        // INF - -INF
-                    Fraction::from(i64::MAX)
-                }
-            } else {
-        // -INF - INF
-                if other.numerator > 0 {
-                    Fraction::from(-i64::MAX)
-                } else {
-        // -INF - -INF
-                    Fraction::from(0)
-                }
-            }
-        } else if self.numerator.abs() == i64::MAX {
-        // INF - other = INF
-            Fraction::from(self.numerator)
-        } else if other.numerator.abs() == i64::MAX {
-        // self - INF = -INF
-            Fraction::from(-other.numerator)
-        } else if self.denominator.abs() == i64::MAX {
-        // 0 - other = -other
-            Fraction {
-                numerator: -other.numerator,
-                denominator: other.denominator,
-            }
-        } else if other.denominator.abs() == i64::MAX {
-        // self - 0 = self
-            Fraction {
-                numerator: self.numerator,
-                denominator: self.denominator,
-            }
-        } else {
-            let mut res = Fraction {
-                numerator: self.numerator*other.denominator - other.numerator*self.denominator,
-                denominator: self.denominator*other.denominator,
-            };
-            res.reduce();
-            res
-        }
+        Fraction(self.0 - other.0)
     }
 }
 
@@ -125,50 +81,7 @@ impl ops::Add for Fraction {
     type Output = Fraction;
 
     fn add(self, other: Fraction) -> Fraction {
-        if self.numerator.abs() == i64::MAX && other.numerator.abs() == i64::MAX {
-            if self.numerator == i64::MAX {
-        // INF + INF
-                if other.numerator == i64::MAX {
-                    Fraction::from(i64::MAX)
-                } else {
-        // INF + -INF
-                    Fraction::from(0)
-                }
-            } else {
-        // -INF + INF
-                if other.numerator == i64::MAX {
-                    Fraction::from(0)
-                } else {
-        // -INF + -INF
-                    Fraction::from(-i64::MAX)
-                }
-            }
-        } else if self.numerator.abs() == i64::MAX {
-        // +-INF + other = +-INF 
-            Fraction::from(self.numerator)
-        } else if other.numerator.abs() == i64::MAX {
-        // self +- INF = +-INF
-            Fraction::from(other.numerator)
-        } else if self.denominator.abs() == i64::MAX {
-        // 0 + other = other
-            Fraction {
-                numerator: other.numerator,
-                denominator: other.denominator,
-            }
-        } else if other.denominator.abs() == i64::MAX {
-        // self + 0 = self
-            Fraction {
-                numerator: self.numerator,
-                denominator: self.denominator,
-            }
-        } else {
-            let mut res = Fraction {
-                numerator: self.numerator*other.denominator + other.numerator*self.denominator,
-                denominator: self.denominator*other.denominator,
-            };
-            res.reduce();
-            res
-        }
+        Fraction(self.0 + other.0)
     }
 }
 
@@ -176,98 +89,7 @@ impl ops::Mul for Fraction {
     type Output = Fraction;
 
     fn mul(self, other: Fraction) -> Fraction {
-        // INF * INF = INF
-        if self.numerator.abs() == i64::MAX && other.numerator.abs() == i64::MAX {
-            if (self.numerator > 0 && other.numerator < 0) || (self.numerator < 0 && other.numerator > 0) {
-                Fraction::from(-i64::MAX)
-            } else {
-                Fraction::from(i64::MAX)
-            }
-        } else if self.numerator.abs() == i64::MAX {
-            if other.denominator.abs() == i64::MAX {
-                if self.numerator > 0 {
-                    if other.numerator > 0 {
-        // INF * 0 = other.numerator/self.denominator
-                        let mut res = Fraction::new(other.numerator, self.denominator);
-                        res.reduce();
-                        res
-                    } else {
-        // INF * -0 = -other.numerator/self.denominator
-                        let mut res = Fraction::new(other.numerator, self.denominator);
-                        res.reduce();
-                        res
-                    }
-                } else {
-                    if other.numerator > 0 {
-        // -INF * 0 = -other.numerator/self.denominator
-                        let mut res = Fraction::new(-other.numerator, self.denominator);
-                        res.reduce();
-                        res
-                    } else {
-        // -INF * -0 = other.numerator/self.denominator
-                        let mut res = Fraction::new(-other.numerator, self.denominator);
-                        res.reduce();
-                        res
-                    }
-                }
-            } else {
-                if other.numerator > 0 {
-        // INF * other = -INF || -INf * other = INF
-                    Fraction::from(self.numerator)
-                } else {
-        // INF * -other = INF || -INF * -other = -INF
-                    Fraction::from(-self.numerator)
-                }
-            }
-        } else if self.denominator.abs() == i64::MAX {
-            if other.numerator.abs() == i64::MAX {
-                if self.numerator > 0 {
-                    if other.numerator > 0 {
-        // 0 * INF = self.numerator/other.denominator
-                        let mut res = Fraction::new(self.numerator, other.denominator);
-                        res.reduce();
-                        res
-                    } else {
-        // 0 * -INF = -self.numerator/other.denominator
-                        let mut res = Fraction::new(-self.numerator, other.denominator);
-                        res.reduce();
-                        res
-                    }
-                } else {
-                    if other.numerator > 0 {
-        // -0 * INF = -self.numerator/other.denominator
-                        let mut res = Fraction::new(self.numerator, other.denominator);
-                        res.reduce();
-                        res
-                    } else {
-        // -0 * -INF = self.numerator/other.denominator
-                        let mut res = Fraction::new(-self.numerator, other.denominator);
-                        res.reduce();
-                        res
-                    }
-                }
-            } else {
-        // 0 * other = 0
-                Fraction::from(0) 
-            }
-        } else if other.numerator.abs() == i64::MAX {
-            if self.numerator > 0 {
-        // self * INF = INF || self * -INF = -INF 
-                Fraction::from(other.numerator)
-            } else {
-        // -self * INF = -INF || -self * -INF = INF
-                Fraction::from(-other.numerator)
-            }
-        } else if other.denominator.abs() == i64::MAX {
-            Fraction::from(0)
-        } else {
-            let mut res = Fraction {
-            numerator: self.numerator * other.numerator,
-            denominator: self.denominator * other.denominator,
-            };
-            res.reduce();
-            res
-        }
+        Fraction(self.0 * other.0)
     }
 }
 
@@ -275,63 +97,8 @@ impl ops::Div for Fraction {
     type Output = Fraction;
 
     fn div(self, other: Fraction) -> Fraction {
-        if self.numerator.abs() == i64::MAX && other.numerator.abs() == i64::MAX {
-            if (self.numerator < 0 && other.numerator > 0) || (self.numerator > 0 && other.numerator < 0) {
-        // INF / -INF  || -INF / INF = -other.denominator/self.denominator
-                let mut res = Fraction::new(-other.denominator, self.denominator);
-                res.reduce();
-                res
-            } else {
-        // INF / INF  || -INF / -INF = other.denominator/self.denominator
-                let mut res = Fraction::new(other.denominator, self.denominator);
-                res.reduce();
-                res
-            }
-        } else if self.numerator.abs() == i64::MAX {
-            if other.numerator < 0 {
-        // INF / -other  = INF  || -INF / -other = INF
-                Fraction::from(-self.numerator)
-            } else {
-        // INF / other = INF  || -INF / other = -INF
-                Fraction::from(self.numerator)
-            }
-        } else if other.numerator.abs() == i64::MAX {
-        // self / INF = 0
-            Fraction::from(0)
-        } else if self.denominator.abs() == i64::MAX && other.denominator.abs() == i64::MAX {
-        // 0 / 0 = self.numerator/other.numerator
-            let mut res = Fraction::new(self.numerator, other.numerator);
-            res.reduce();
-            res
-        } else if other.denominator.abs() == i64::MAX {
-            if self.numerator > 0 {
-                if other.numerator > 0 {
-        // self / 0 = INF
-                    Fraction::from(i64::MAX)
-                } else {
-        // self / -0 = -INF
-                    Fraction::from(-i64::MAX)
-                }
-            } else {
-                if other.numerator > 0 {
-        // -self / 0 = INF
-                    Fraction::from(-i64::MAX)
-                } else {
-        // -self / -0 = INF
-                    Fraction::from(i64::MAX)
-                }
-            }
-        } else if self.denominator.abs() == i64::MAX {
-        // 0 / other = 0
-            Fraction::from(0)
-        } else {
-            let mut res = Fraction {
-                numerator: self.numerator * other.denominator,
-                denominator: self.denominator * other.numerator,
-            };
-            res.reduce();
-            res
-        }
+        assert!(!other.is_zero(), "Cannot divide a fraction by 0.");
+        Fraction(self.0 / other.0)
     }
 }
 
@@ -339,79 +106,226 @@ impl ops::Neg for Fraction {
     type Output = Fraction;
 
     fn neg(self) -> Fraction {
-        Fraction {
-            numerator: -self.numerator,
-            denominator: self.denominator,
-        }
+        Fraction(-self.0)
     }
 }
 
 impl PartialOrd for Fraction {
     fn partial_cmp(&self, other: &Fraction) -> Option<cmp::Ordering> {
-        if self.numerator.abs() == i64::MAX || other.numerator.abs() == i64::MAX {
-            Some(self.numerator.cmp(&other.numerator))
-        } else if self.denominator.abs() == i64::MAX || other.denominator.abs() == i64::MAX {
-            Some(other.denominator.cmp(&self.denominator))
-        } else {
-            Some((self.numerator*other.denominator).cmp(&(other.numerator*self.denominator)))
-        }
+        Some(self.0.cmp(&other.0))
     }
 }
 
 impl From<i64> for Fraction {
     fn from(i: i64) -> Fraction {
-        Fraction {
-            numerator: i,
-            denominator: 1i64,
-        }
+        Fraction(BigRational::from_integer(BigInt::from(i)))
     }
 }
 
+// panics on NaN and infinity, which Fraction::try_from_f64 returns as an error
 impl From<f64> for Fraction {
     fn from(f: f64) -> Fraction {
-        let mut power: u32 = 0;
-        let mut n: f64 = f;
-        while n != n.floor() {
-            n *= 10f64;
-            power += 1;
+        match Fraction::try_from_f64(f) {
+            Ok(value) => value,
+            Err(_) => panic!("Cannot create a fraction from {}.", f),
         }
-        let mut res = Fraction {
-            numerator: n as i64,
-            denominator: 10i32.pow(power) as i64,
-        };
-        res.reduce();
-        res
+    }
+}
+
+impl From<BigRational> for Fraction {
+    fn from(value: BigRational) -> Fraction {
+        Fraction(value)
+    }
+}
+
+impl From<Fraction> for BigRational {
+    fn from(frac: Fraction) -> BigRational {
+        frac.0
     }
 }
 
 impl From<Fraction> for f64 {
     fn from(frac: Fraction) -> f64 {
-        frac.numerator as f64 / frac.denominator as f64 
+        ToPrimitive::to_f64(&frac.0).unwrap_or(f64::NAN)
+    }
+}
+
+// A Fraction, or any other Scalar, extended with +-infinity, which is where the ratio tests start from.
+// The variants are listed in order, so NegInf < Finite(_) < PosInf.
+// The indeterminate forms INF - INF, 0 * INF, INF / INF and x / 0 panic, the same as dividing a Fraction by 0
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub enum ExtendedFraction<T: Scalar = Fraction> {
+    NegInf,
+    Finite(T),
+    PosInf,
+}
+
+impl<T: Scalar> ExtendedFraction<T> {
+    pub fn is_infinite(&self) -> bool {
+        !self.is_finite()
+    }
+
+    pub fn is_finite(&self) -> bool {
+        matches!(self, ExtendedFraction::Finite(_))
+    }
+
+    // the value, or None when it is infinite
+    pub fn finite(&self) -> Option<&T> {
+        match self {
+            ExtendedFraction::Finite(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn abs(&self) -> Self {
+        match self {
+            ExtendedFraction::Finite(value) => ExtendedFraction::Finite(value.abs()),
+            _ => ExtendedFraction::PosInf,
+        }
+    }
+
+    // -1, 0 or 1
+    fn signum(&self) -> i64 {
+        match self {
+            ExtendedFraction::NegInf => -1,
+            ExtendedFraction::PosInf => 1,
+            ExtendedFraction::Finite(value) => value.sign(T::tolerance()),
+        }
+    }
+
+    fn infinity(sign: i64) -> ExtendedFraction<T> {
+        if sign > 0 {
+            ExtendedFraction::PosInf
+        } else {
+            ExtendedFraction::NegInf
+        }
+    }
+}
+
+impl<T: Scalar> fmt::Display for ExtendedFraction<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExtendedFraction::NegInf => write!(f, "-inf"),
+            ExtendedFraction::Finite(value) => write!(f, "{}", value),
+            ExtendedFraction::PosInf => write!(f, "inf"),
+        }
+    }
+}
+
+impl<T: Scalar> ops::Add for ExtendedFraction<T> {
+    type Output = ExtendedFraction<T>;
+
+    fn add(self, other: ExtendedFraction<T>) -> ExtendedFraction<T> {
+        match (self, other) {
+            (ExtendedFraction::Finite(a), ExtendedFraction::Finite(b)) => ExtendedFraction::Finite(a + b),
+            // INF + -INF
+            (ExtendedFraction::PosInf, ExtendedFraction::NegInf) | (ExtendedFraction::NegInf, ExtendedFraction::PosInf) => panic!("Cannot add infinities of opposite signs."),
+            // +-INF + other = +-INF
+            (ExtendedFraction::Finite(_), infinite) | (infinite, _) => infinite,
+        }
+    }
+}
+
+impl<T: Scalar> ops::Sub for ExtendedFraction<T> {
+    type Output = ExtendedFraction<T>;
+
+    fn sub(self, other: ExtendedFraction<T>) -> ExtendedFraction<T> {
+        self + -other
+    }
+}
+
+impl<T: Scalar> ops::Mul for ExtendedFraction<T> {
+    type Output = ExtendedFraction<T>;
+
+    fn mul(self, other: ExtendedFraction<T>) -> ExtendedFraction<T> {
+        match (self, other) {
+            (ExtendedFraction::Finite(a), ExtendedFraction::Finite(b)) => ExtendedFraction::Finite(a * b),
+            (a, b) => {
+                let sign = a.signum() * b.signum();
+                assert_ne!(sign, 0, "Cannot multiply infinity by 0.");
+                ExtendedFraction::infinity(sign)
+            }
+        }
+    }
+}
+
+impl<T: Scalar> ops::Div for ExtendedFraction<T> {
+    type Output = ExtendedFraction<T>;
+
+    fn div(self, other: ExtendedFraction<T>) -> ExtendedFraction<T> {
+        match (self, other) {
+            (ExtendedFraction::Finite(a), ExtendedFraction::Finite(b)) => ExtendedFraction::Finite(a / b),
+            // self / INF = 0
+            (ExtendedFraction::Finite(_), _) => ExtendedFraction::Finite(T::zero()),
+            (a, ExtendedFraction::Finite(b)) => {
+                assert_ne!(b.sign(T::tolerance()), 0, "Cannot divide infinity by 0.");
+                ExtendedFraction::infinity(a.signum() * ExtendedFraction::Finite(b).signum())
+            }
+            _ => panic!("Cannot divide infinity by infinity."),
+        }
+    }
+}
+
+impl<T: Scalar> ops::Neg for ExtendedFraction<T> {
+    type Output = ExtendedFraction<T>;
+
+    fn neg(self) -> ExtendedFraction<T> {
+        match self {
+            ExtendedFraction::NegInf => ExtendedFraction::PosInf,
+            ExtendedFraction::Finite(value) => ExtendedFraction::Finite(-value),
+            ExtendedFraction::PosInf => ExtendedFraction::NegInf,
+        }
     }
 }
 
+impl<T: Scalar> From<T> for ExtendedFraction<T> {
+    fn from(value: T) -> ExtendedFraction<T> {
+        ExtendedFraction::Finite(value)
+    }
+}
+
+impl From<i64> for ExtendedFraction {
+    fn from(i: i64) -> ExtendedFraction {
+        ExtendedFraction::Finite(Fraction::from(i))
+    }
+}
+
+impl<T: Scalar> From<ExtendedFraction<T>> for f64 {
+    fn from(value: ExtendedFraction<T>) -> f64 {
+        match value {
+            ExtendedFraction::NegInf => f64::NEG_INFINITY,
+            ExtendedFraction::Finite(value) => value.to_f64(),
+            ExtendedFraction::PosInf => f64::INFINITY,
+        }
+    }
+}
+
+#[cfg(test)]
 mod fraction_test {
-    use super::Fraction;
+    use super::{Fraction, ExtendedFraction};
+    use super::super::error::SimplexError;
+    const INF: ExtendedFraction = ExtendedFraction::PosInf;
+    const NEG_INF: ExtendedFraction = ExtendedFraction::NegInf;
+    fn ext(i: i64) -> ExtendedFraction {
+        ExtendedFraction::from(i)
+    }
     #[test]
     fn add_inf2(){
-        assert_eq!(Fraction::from(0), Fraction::from(i64::MAX) + Fraction::from(-i64::MAX), "Failed INF + -INF.");
-        assert_eq!(Fraction::from(0), Fraction::from(-i64::MAX) + Fraction::from(i64::MAX), "Failed -INF + INF.");
-        assert_eq!(Fraction::from(i64::MAX), Fraction::from(i64::MAX) + Fraction::from(i64::MAX), "Failed INF + INF.");
-        assert_eq!(Fraction::from(-i64::MAX), Fraction::from(-i64::MAX) + Fraction::from(-i64::MAX), "Failed -INF + -INF.");
+        assert_eq!(INF, INF + INF, "Failed INF + INF.");
+        assert_eq!(NEG_INF, NEG_INF + NEG_INF, "Failed -INF + -INF.");
     }
     #[test]
-    fn add_inf(){
-        assert_eq!(Fraction::from(i64::MAX), Fraction::from(i64::MAX) + Fraction::from(10), "Failed INF + other.");
-        assert_eq!(Fraction::from(-i64::MAX), Fraction::from(-i64::MAX) + Fraction::from(10), "Failed -INF + other.");
-        assert_eq!(Fraction::from(i64::MAX), Fraction::from(10) + Fraction::from(i64::MAX), "Failed self + INF.");
-        assert_eq!(Fraction::from(-i64::MAX), Fraction::from(10) + Fraction::from(-i64::MAX), "Failed self + -INF.");
+    #[should_panic]
+    fn add_inf_neg_inf(){
+        let _ = INF + NEG_INF;
     }
     #[test]
-    fn add_0(){
-        assert_eq!(Fraction::from(8), Fraction::new(1,i64::MAX) + Fraction::from(8), "Failed 0 + other.");
-        assert_eq!(Fraction::from(-8), Fraction::new(1,i64::MAX) + Fraction::from(-8), "Failed 0 + -other.");
-        assert_eq!(Fraction::from(7), Fraction::from(7) + Fraction::new(1,i64::MAX), "Failed self + 0.");
-        assert_eq!(Fraction::from(-7), Fraction::from(-7) + Fraction::new(1,i64::MAX), "Failed -self + 0.");
+    fn add_inf(){
+        assert_eq!(INF, INF + ext(10), "Failed INF + other.");
+        assert_eq!(NEG_INF, NEG_INF + ext(10), "Failed -INF + other.");
+        assert_eq!(INF, ext(10) + INF, "Failed self + INF.");
+        assert_eq!(NEG_INF, ext(10) + NEG_INF, "Failed self + -INF.");
+        assert_eq!(ext(17), ext(7) + ext(10), "Failed finite addition.");
     }
     #[test]
     fn add_standard(){
@@ -422,24 +336,20 @@ mod fraction_test {
     }
     #[test]
     fn sub_inf2(){
-        assert_eq!(Fraction::from(0), Fraction::from(i64::MAX) - Fraction::from(i64::MAX), "Failed INF - INF.");
-        assert_eq!(Fraction::from(i64::MAX), Fraction::from(i64::MAX) - Fraction::from(-i64::MAX), "Failed INF - -INF.");
-        assert_eq!(Fraction::from(-i64::MAX), Fraction::from(-i64::MAX) - Fraction::from(i64::MAX), "Failed -INF - INF.");
-        assert_eq!(Fraction::from(0), Fraction::from(-i64::MAX) - Fraction::from(-i64::MAX), "Failed -INF - -INF.");
+        assert_eq!(INF, INF - NEG_INF, "Failed INF - -INF.");
+        assert_eq!(NEG_INF, NEG_INF - INF, "Failed -INF - INF.");
     }
     #[test]
-    fn sub_inf(){
-        assert_eq!(Fraction::from(i64::MAX), Fraction::from(i64::MAX) - Fraction::from(10), "Failed INF - other.");
-        assert_eq!(Fraction::from(-i64::MAX), Fraction::from(-i64::MAX) - Fraction::from(10), "Failed -INF - other.");
-        assert_eq!(Fraction::from(-i64::MAX), Fraction::from(10) - Fraction::from(i64::MAX), "Failed self - INF.");
-        assert_eq!(Fraction::from(i64::MAX), Fraction::from(10) - Fraction::from(-i64::MAX), "Failed self - -INF.");
+    #[should_panic]
+    fn sub_inf_inf(){
+        let _ = INF - INF;
     }
     #[test]
-    fn sub_0(){
-        assert_eq!(Fraction::from(-8), Fraction::new(1,i64::MAX) - Fraction::from(8), "Failed 0 - other.");
-        assert_eq!(Fraction::from(8), Fraction::new(1,i64::MAX) - Fraction::from(-8), "Failed 0 - -other.");
-        assert_eq!(Fraction::from(7), Fraction::from(7) - Fraction::new(1,i64::MAX), "Failed self - 0.");
-        assert_eq!(Fraction::from(-7), Fraction::from(-7) - Fraction::new(1,i64::MAX), "Failed -self - 0.");
+    fn sub_inf(){
+        assert_eq!(INF, INF - ext(10), "Failed INF - other.");
+        assert_eq!(NEG_INF, NEG_INF - ext(10), "Failed -INF - other.");
+        assert_eq!(NEG_INF, ext(10) - INF, "Failed self - INF.");
+        assert_eq!(INF, ext(10) - NEG_INF, "Failed self - -INF.");
     }
     #[test]
     fn sub_standard(){
@@ -450,40 +360,26 @@ mod fraction_test {
     }
     #[test]
     fn mul_inf2(){
-        assert_eq!(Fraction::from(i64::MAX), Fraction::from(i64::MAX) * Fraction::from(i64::MAX), "Failed INF * INF.");
-        assert_eq!(Fraction::from(-i64::MAX), Fraction::from(i64::MAX) * Fraction::from(-i64::MAX), "Failed INF * -INF.");
-        assert_eq!(Fraction::from(-i64::MAX), Fraction::from(-i64::MAX) * Fraction::from(i64::MAX), "Failed -INF * INF.");
-        assert_eq!(Fraction::from(i64::MAX), Fraction::from(-i64::MAX) * Fraction::from(-i64::MAX), "Failed -INF * -INF.");
+        assert_eq!(INF, INF * INF, "Failed INF * INF.");
+        assert_eq!(NEG_INF, INF * NEG_INF, "Failed INF * -INF.");
+        assert_eq!(NEG_INF, NEG_INF * INF, "Failed -INF * INF.");
+        assert_eq!(INF, NEG_INF * NEG_INF, "Failed -INF * -INF.");
     }
     #[test]
+    #[should_panic]
     fn mul_inf0(){
-        assert_eq!(Fraction::new(3,7), Fraction::new(i64::MAX, 7) * Fraction::new(3,i64::MAX), "Failed INF * 0.");
-        assert_eq!(Fraction::new(-3,7), Fraction::new(-i64::MAX, 7) * Fraction::new(3,i64::MAX), "Failed -INF * 0.");
-        assert_eq!(Fraction::new(-3,7), Fraction::new(i64::MAX, 7) * Fraction::new(-3,i64::MAX), "Failed INF * -0.");
-        assert_eq!(Fraction::new(3,7), Fraction::new(-i64::MAX, 7) * Fraction::new(-3,i64::MAX), "Failed -INF * -0.");
-    }
-    #[test]
-    fn mul_0inf(){
-        assert_eq!(Fraction::new(7,3), Fraction::new(7,i64::MAX) * Fraction::new(i64::MAX,3), "Failed 0 * INF.");
-        assert_eq!(Fraction::new(-7,3), Fraction::new(-7,i64::MAX) * Fraction::new(i64::MAX,3), "Failed -0 * INF.");
-        assert_eq!(Fraction::new(-7,3), Fraction::new(7,i64::MAX) * Fraction::new(-i64::MAX,3), "Failed 0 * -INF.");
-        assert_eq!(Fraction::new(7,3), Fraction::new(-7,i64::MAX) * Fraction::new(-i64::MAX,3), "Failed -0 * -INF.");
+        let _ = INF * ext(0);
     }
     #[test]
     fn mul_inf(){
-        assert_eq!(Fraction::from(-i64::MAX), Fraction::from(i64::MAX) * Fraction::from(-2), "Failed INF * -other.");
-        assert_eq!(Fraction::from(-i64::MAX), Fraction::from(-i64::MAX) * Fraction::from(2), "Failed -INF * other.");
-        assert_eq!(Fraction::from(i64::MAX), Fraction::from(-i64::MAX) * Fraction::from(-2), "Failed -INF * -other.");
-        assert_eq!(Fraction::from(i64::MAX), Fraction::from(i64::MAX) * Fraction::from(2), "Failed INF * other.");
-        assert_eq!(Fraction::from(-i64::MAX), Fraction::from(-10) * Fraction::from(i64::MAX), "Failed -self * INF.");
-        assert_eq!(Fraction::from(i64::MAX), Fraction::from(-10) * Fraction::from(-i64::MAX), "Failed -self * -INF.");
-        assert_eq!(Fraction::from(i64::MAX), Fraction::from(10) * Fraction::from(i64::MAX), "Failed self * INF.");
-        assert_eq!(Fraction::from(-i64::MAX), Fraction::from(10) * Fraction::from(-i64::MAX), "Failed self * -INF.");
-    }
-    #[test]
-    fn mul_0(){
-        assert_eq!(Fraction::from(0), Fraction::new(1,i64::MAX) * Fraction::from(8), "Failed 0 * other.");
-        assert_eq!(Fraction::from(0), Fraction::from(7) * Fraction::new(1,i64::MAX), "Failed self * 0.");
+        assert_eq!(NEG_INF, INF * ext(-2), "Failed INF * -other.");
+        assert_eq!(NEG_INF, NEG_INF * ext(2), "Failed -INF * other.");
+        assert_eq!(INF, NEG_INF * ext(-2), "Failed -INF * -other.");
+        assert_eq!(INF, INF * ext(2), "Failed INF * other.");
+        assert_eq!(NEG_INF, ext(-10) * INF, "Failed -self * INF.");
+        assert_eq!(INF, ext(-10) * NEG_INF, "Failed -self * -INF.");
+        assert_eq!(INF, ext(10) * INF, "Failed self * INF.");
+        assert_eq!(NEG_INF, ext(10) * NEG_INF, "Failed self * -INF.");
     }
     #[test]
     fn mul_standard(){
@@ -493,39 +389,23 @@ mod fraction_test {
         assert_eq!(Fraction::from(21), Fraction::from(-7) * Fraction::from(-3), "Failed regular addition.");
     }
     #[test]
+    #[should_panic]
     fn div_inf2(){
-        assert_eq!(Fraction::new(3,7), Fraction::new(i64::MAX, 7) / Fraction::new(i64::MAX, 3), "Failed INF / INF.");
-        assert_eq!(Fraction::new(-3,7), Fraction::new(-i64::MAX, 7) / Fraction::new(i64::MAX, 3), "Failed -INF / INF.");
-        assert_eq!(Fraction::new(-3,7), Fraction::new(i64::MAX, 7) / Fraction::new(-i64::MAX, 3), "Failed INF / -INF.");
-        assert_eq!(Fraction::new(3,7), Fraction::new(-i64::MAX, 7) / Fraction::new(-i64::MAX, 3), "Failed -INF / -INF.");
-    }
-    #[test]
-    fn div_inf0(){
-        assert_eq!(Fraction::from(i64::MAX), Fraction::new(i64::MAX, 7) / Fraction::new(3,i64::MAX), "Failed INF / 0.");
-        assert_eq!(Fraction::from(-i64::MAX), Fraction::new(-i64::MAX, 7) / Fraction::new(3,i64::MAX), "Failed -INF / 0.");
-        assert_eq!(Fraction::from(-i64::MAX), Fraction::new(i64::MAX, 7) / Fraction::new(-3,i64::MAX), "Failed INF / -0.");
-        assert_eq!(Fraction::from(i64::MAX), Fraction::new(-i64::MAX, 7) / Fraction::new(-3,i64::MAX), "Failed -INF / -0.");
-    }
-    #[test]
-    fn div_0inf(){
-        assert_eq!(Fraction::from(0), Fraction::new(7,i64::MAX) / Fraction::new(i64::MAX,3), "Failed 0 / INF.");
+        let _ = INF / NEG_INF;
     }
     #[test]
     fn div_inf(){
-        assert_eq!(Fraction::from(-i64::MAX), Fraction::from(i64::MAX) / Fraction::from(-2), "Failed INF / -other.");
-        assert_eq!(Fraction::from(i64::MAX), Fraction::from(-i64::MAX) / Fraction::from(-2), "Failed -INF / -other.");
-        assert_eq!(Fraction::from(i64::MAX), Fraction::from(i64::MAX) / Fraction::from(2), "Failed INF / other.");
-        assert_eq!(Fraction::from(-i64::MAX), Fraction::from(-i64::MAX) / Fraction::from(2), "Failed -INF / other.");
-        assert_eq!(Fraction::from(0), Fraction::from(-10) / Fraction::from(i64::MAX), "Failed -self / INF.");
-        assert_eq!(Fraction::from(0), Fraction::from(10) / Fraction::from(i64::MAX), "Failed self / INF.");
+        assert_eq!(NEG_INF, INF / ext(-2), "Failed INF / -other.");
+        assert_eq!(INF, NEG_INF / ext(-2), "Failed -INF / -other.");
+        assert_eq!(INF, INF / ext(2), "Failed INF / other.");
+        assert_eq!(NEG_INF, NEG_INF / ext(2), "Failed -INF / other.");
+        assert_eq!(ext(0), ext(-10) / INF, "Failed -self / INF.");
+        assert_eq!(ext(0), ext(10) / NEG_INF, "Failed self / -INF.");
     }
     #[test]
+    #[should_panic]
     fn div_0(){
-        assert_eq!(Fraction::from(0), Fraction::new(1,i64::MAX) / Fraction::from(8), "Failed 0 / other.");
-        assert_eq!(Fraction::from(i64::MAX), Fraction::from(7) / Fraction::new(1,i64::MAX), "Failed self / 0.");
-        assert_eq!(Fraction::from(-i64::MAX), Fraction::from(-7) / Fraction::new(1,i64::MAX), "Failed -self / 0.");
-        assert_eq!(Fraction::from(-i64::MAX), Fraction::from(7) / Fraction::new(-1,i64::MAX), "Failed self / -0.");
-        assert_eq!(Fraction::from(i64::MAX), Fraction::from(-7) / Fraction::new(-1,i64::MAX), "Failed -self / -0.");
+        let _ = Fraction::from(7) / Fraction::from(0);
     }
     #[test]
     fn div_standard(){
@@ -538,23 +418,74 @@ mod fraction_test {
     fn comparisons() {
         assert!(Fraction::new(1,3) > Fraction::new(1, 4));
         assert!(Fraction::new(1,6) > Fraction::new(1, 10));
+        assert!(Fraction::new(-1,3) < Fraction::new(1, -4));
         assert!(Fraction::new(1,i64::MAX) < Fraction::new(1, 4));
-        assert!(Fraction::from(i64::MAX) > Fraction::from(4));
-        assert!(Fraction::from(-i64::MAX) < Fraction::new(1, 4));
-        assert!(Fraction::from(-i64::MAX) == Fraction::from(-i64::MAX));
-        assert!(Fraction::from(1) > Fraction::from(-i64::MAX));
-        assert!(Fraction::from(-i64::MAX) < Fraction::new(1,-i64::MAX));
-        assert!(Fraction::from(i64::MAX) > Fraction::new(1,i64::MAX));
+        assert!(Fraction::new(1,i64::MAX) > Fraction::from(0));
+        assert!(INF > ext(4));
+        assert!(NEG_INF < ExtendedFraction::from(Fraction::new(1, 4)));
+        assert!(NEG_INF == NEG_INF);
+        assert!(ext(1) > NEG_INF);
+        assert!(NEG_INF < INF);
+        assert!(ExtendedFraction::from(Fraction::from(i64::MAX)) < INF);
+        assert!(ext(-3) < ext(2));
+    }
+    #[test]
+    fn i64_max_is_finite() {
+        // i64::MAX is an ordinary number, and infinity is only written with ExtendedFraction
+        assert_eq!(Fraction::from(0), Fraction::from(i64::MAX) - Fraction::from(i64::MAX));
+        assert_eq!(Fraction::from(1), Fraction::from(i64::MAX) / Fraction::from(i64::MAX));
+        assert_eq!(Fraction::from(i64::MAX), Fraction::new(i64::MAX, 2) * Fraction::from(2));
+        assert_eq!(Fraction::from(0), Fraction::new(1,i64::MAX) * Fraction::from(0));
+        assert!(ExtendedFraction::from(Fraction::from(i64::MAX)).is_finite());
     }
     #[test]
     fn negation() {
         assert_eq!(Fraction::from(1), -Fraction::from(-1));
         assert_eq!(Fraction::from(-7), -Fraction::from(7));
+        assert_eq!(NEG_INF, -INF);
+        assert_eq!(ext(-7), -ext(7));
+    }
+    #[test]
+    fn checked_constructors() {
+        assert_eq!(Ok(Fraction::new(1,2)), Fraction::try_new(2,4));
+        assert_eq!(Err(SimplexError::ZeroDenominator), Fraction::try_new(2,0));
+        assert_eq!(Ok(Fraction::new(-5,4)), Fraction::try_from_f64(-1.25f64));
+        assert!(Fraction::try_from_f64(f64::NAN).is_err());
+        assert_eq!(Err(SimplexError::UnrepresentableValue(f64::NEG_INFINITY)), Fraction::try_from_f64(f64::NEG_INFINITY));
+        // every finite f64 is read in exactly, however large or small
+        assert_eq!(Ok(Fraction::new(3602879701896397, 36028797018963968)), Fraction::try_from_f64(0.1f64));
+        for f in [1e30f64, 1e-25f64, f64::MAX, -f64::MIN_POSITIVE, 5e-324f64].iter() {
+            assert_eq!(*f, f64::from(Fraction::try_from_f64(*f).unwrap()));
+        }
+    }
+    #[test]
+    #[should_panic]
+    fn from_nan(){
+        let _ = Fraction::from(f64::NAN);
     }
     #[test]
     fn absolute() {
         assert_eq!(Fraction::new(3,4), Fraction::new(-3, 4).abs());
         assert_eq!(Fraction::new(4,3), Fraction::new(4,-3).abs());
         assert_eq!(Fraction::new(3,7), Fraction::new(3,7).abs());
+        assert_eq!(INF, NEG_INF.abs());
+    }
+    #[test]
+    fn extended_display() {
+        assert_eq!("inf", format!("{}", INF));
+        assert_eq!("-inf", format!("{}", NEG_INF));
+        assert_eq!("-3/4", format!("{}", ExtendedFraction::from(Fraction::new(3,-4))));
+        assert_eq!(f64::NEG_INFINITY, f64::from(NEG_INF));
+    }
+    #[test]
+    fn large_values() {
+        // these products do not fit in an i64, but stay exact
+        let big = Fraction::new(i64::MAX - 1, 5);
+        let product = big.clone() * big.clone() * big.clone();
+        assert_eq!(format!("{}", product), "784637716923335094969050127519550606928412983852609306616/125");
+        assert_eq!(product.clone() / big.clone() / big.clone(), big);
+        assert!(product.clone() > big.clone() * big.clone());
+        assert_eq!(product.clone() - product.clone() + Fraction::new(1,2), Fraction::new(1,2));
+        assert!(f64::from(product) > 6e54f64);
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,490 @@
+use std::ops;
+use super::fraction::Fraction;
+use super::tableau::Tableau;
+use super::solution::{Solution, SolveStatus};
+use super::error::SimplexError;
+use super::options::{SolverOptions, SolveType};
+
+// A handle to a variable that was added to a Model
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Variable {
+    index: usize,
+}
+
+// A sum of variables multiplied by coefficients, like 3x + 5y
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinearExpression {
+    terms: Vec<(Variable, f64)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConstraintType {
+    LessThanOrEqual,
+    GreaterThanOrEqual,
+    Equal,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Constraint {
+    expression: LinearExpression,
+    constraint_type: ConstraintType,
+    rhs: f64,
+}
+
+// the column major A, b and c, and the bounds of each column, that are passed to Tableau::with_bounds
+struct StandardForm {
+    A: Vec<Vec<f64>>,
+    b: Vec<f64>,
+    c: Vec<f64>,
+    lower: Vec<f64>,
+    upper: Vec<f64>,
+    // the rows that each constraint became, along with the sign that the constraint was multiplied by
+    constraint_rows: Vec<Vec<(usize, f64)>>,
+}
+
+// A linear program written with named variables and general constraints.
+// The slack, surplus and artificial variables needed for the Tableau are added when the model is built.
+//
+// let mut model = Model::new();
+// let x = model.add_variable("x");
+// let y = model.add_variable("y");
+// model.set_objective(3.0*x + 5.0*y);
+// model.add_constraint(x + 2.0*y, ConstraintType::LessThanOrEqual, 14.0);
+// model.add_constraint(3.0*x - y, ConstraintType::GreaterThanOrEqual, 0.0);
+// let solution = model.solve(&SolverOptions::new())?;
+#[derive(Debug, Clone, PartialEq)]
+pub struct Model {
+    variables: Vec<String>,
+    bounds: Vec<(f64, f64)>,
+    objective: LinearExpression,
+    constraints: Vec<Constraint>,
+}
+
+// The solution of a Model, with the values of the variables given by name
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelSolution {
+    pub status: SolveStatus,
+    pub objective: Fraction,
+    pub values: Vec<(String, Fraction)>,
+    // the shadow price of each constraint, in the order they were added
+    pub duals: Option<Vec<Fraction>>,
+    pub reduced_costs: Option<Vec<(String, Fraction)>>,
+    // the solution of the underlying Tableau, including the slack and surplus variables
+    pub tableau_solution: Solution,
+}
+
+impl LinearExpression {
+    pub fn new() -> LinearExpression {
+        LinearExpression {
+            terms: Vec::new(),
+        }
+    }
+
+    pub fn term(mut self, variable: Variable, coefficient: f64) -> LinearExpression {
+        self.terms.push((variable, coefficient));
+        self
+    }
+
+    // the total coefficient of each variable, adding together any repeated terms
+    fn coefficients(&self, number_of_variables: usize) -> Result<Vec<f64>, SimplexError> {
+        let mut coefficients = vec![0f64;number_of_variables];
+        for (variable, coefficient) in self.terms.iter() {
+            if variable.index >= number_of_variables {
+                return Err(SimplexError::UnknownVariable(variable.index));
+            }
+            coefficients[variable.index] += coefficient;
+        }
+        Ok(coefficients)
+    }
+}
+
+impl Default for LinearExpression {
+    fn default() -> LinearExpression {
+        LinearExpression::new()
+    }
+}
+
+impl Model {
+    pub fn new() -> Model {
+        Model {
+            variables: Vec::new(),
+            bounds: Vec::new(),
+            objective: LinearExpression::new(),
+            constraints: Vec::new(),
+        }
+    }
+
+    // adds a variable that must be >= 0
+    pub fn add_variable(&mut self, name: &str) -> Variable {
+        self.add_bounded_variable(name, 0f64, f64::INFINITY)
+    }
+
+    // adds a variable with lower <= x <= upper. Either bound can be infinite, so f64::NEG_INFINITY and f64::INFINITY give a free variable.
+    // The bounds are handled by the simplex method itself, so they don't add any constraints to the tableau
+    pub fn add_bounded_variable(&mut self, name: &str, lower: f64, upper: f64) -> Variable {
+        self.variables.push(String::from(name));
+        self.bounds.push((lower, upper));
+        Variable {
+            index: self.variables.len()-1,
+        }
+    }
+
+    // the objective function, which is maximized or minimized depending on the objective_sense of the SolverOptions
+    pub fn set_objective<E: Into<LinearExpression>>(&mut self, objective: E) {
+        self.objective = objective.into();
+    }
+
+    pub fn add_constraint<E: Into<LinearExpression>>(&mut self, expression: E, constraint_type: ConstraintType, rhs: f64) {
+        self.constraints.push(Constraint {
+            expression: expression.into(),
+            constraint_type,
+            rhs,
+        });
+    }
+
+    pub fn variable_name(&self, variable: Variable) -> &str {
+        &self.variables[variable.index]
+    }
+
+    // builds the column major A, b and c for a Tableau.
+    // Each <= constraint gets a slack variable, each >= constraint gets a surplus and an artificial variable, and each = constraint gets an artificial variable.
+    // The dual simplex method cannot use artificial variables, so there every constraint is written as one or two <= constraints with a slack variable instead.
+    fn standard_form(&self, options: &SolverOptions) -> Result<StandardForm, SimplexError> {
+        let n = self.variables.len();
+
+        // each row is (coefficients, rhs, sign of the slack or surplus variable, needs an artificial variable)
+        let mut rows: Vec<(Vec<f64>, f64, f64, bool)> = Vec::new();
+        let mut constraint_rows = Vec::with_capacity(self.constraints.len());
+        for constraint in self.constraints.iter() {
+            let coefficients = constraint.expression.coefficients(n)?;
+            let rhs = constraint.rhs;
+            let negated: Vec<f64> = coefficients.iter().map(|a| -a).collect();
+            if options.solve_type == SolveType::Dual {
+                match constraint.constraint_type {
+                    ConstraintType::LessThanOrEqual => {
+                        constraint_rows.push(vec![(rows.len(), 1f64)]);
+                        rows.push((coefficients, rhs, 1f64, false));
+                    },
+                    ConstraintType::GreaterThanOrEqual => {
+                        constraint_rows.push(vec![(rows.len(), -1f64)]);
+                        rows.push((negated, -rhs, 1f64, false));
+                    },
+                    ConstraintType::Equal => {
+                        constraint_rows.push(vec![(rows.len(), 1f64), (rows.len()+1, -1f64)]);
+                        rows.push((coefficients, rhs, 1f64, false));
+                        rows.push((negated, -rhs, 1f64, false));
+                    },
+                }
+                continue;
+            }
+
+            // a row with a negative rhs is multiplied by -1 by the tableau, which also gives it an artificial variable if its slack no longer fits
+            constraint_rows.push(vec![(rows.len(), 1f64)]);
+            match constraint.constraint_type {
+                ConstraintType::LessThanOrEqual => {rows.push((coefficients, rhs, 1f64, false));},
+                ConstraintType::GreaterThanOrEqual => {rows.push((coefficients, rhs, -1f64, true));},
+                ConstraintType::Equal => {rows.push((coefficients, rhs, 0f64, true));},
+            }
+        }
+        let m = rows.len();
+
+        let mut A: Vec<Vec<f64>> = Vec::new();
+        let mut c: Vec<f64> = self.objective.coefficients(n)?;
+        let b: Vec<f64> = rows.iter().map(|row| row.1).collect();
+        let mut lower: Vec<f64> = self.bounds.iter().map(|bounds| bounds.0).collect();
+        let mut upper: Vec<f64> = self.bounds.iter().map(|bounds| bounds.1).collect();
+
+        // the original variables
+        for col in 0..n {
+            A.push(rows.iter().map(|row| row.0[col]).collect());
+        }
+        // slack and surplus variables
+        for row in 0..m {
+            if rows[row].2 != 0f64 {
+                let mut column = vec![0f64;m];
+                column[row] = rows[row].2;
+                A.push(column);
+                c.push(0f64);
+                lower.push(0f64);
+                upper.push(f64::INFINITY);
+            }
+        }
+        // artificial variables, which are always placed last so that removing them does not move any other columns
+        for row in 0..m {
+            if rows[row].3 {
+                let mut column = vec![0f64;m];
+                column[row] = 1f64;
+                A.push(column);
+                c.push(f64::MAX);
+                lower.push(0f64);
+                upper.push(f64::INFINITY);
+            }
+        }
+
+        Ok(StandardForm { A, b, c, lower, upper, constraint_rows })
+    }
+
+    pub fn build(&self, options: &SolverOptions) -> Result<Tableau, SimplexError> {
+        let standard_form = self.standard_form(options)?;
+        let A: Vec<&[f64]> = standard_form.A.iter().map(|col| col.as_slice()).collect();
+        Tableau::with_bounds(&A, &standard_form.b, &standard_form.c, &standard_form.lower, &standard_form.upper, options)
+    }
+
+    pub fn solve(&self, options: &SolverOptions) -> Result<ModelSolution, SimplexError> {
+        let standard_form = self.standard_form(options)?;
+        let A: Vec<&[f64]> = standard_form.A.iter().map(|col| col.as_slice()).collect();
+        let mut tableau = Tableau::with_bounds(&A, &standard_form.b, &standard_form.c, &standard_form.lower, &standard_form.upper, options)?;
+        let solution = tableau.solve()?;
+
+        // the original variables are the first columns of the tableau
+        let mut values = Vec::with_capacity(self.variables.len());
+        if solution.status != SolveStatus::Infeasible {
+            for i in 0..self.variables.len() {
+                values.push((self.variables[i].clone(), solution.values[i].clone()));
+            }
+        }
+
+        // a constraint that was multiplied by -1 has the opposite shadow price to its row, and an = constraint that was split into two rows adds them together
+        let duals = solution.duals.as_ref().map(|row_duals| {
+            standard_form.constraint_rows.iter().map(|constraint_rows| {
+                let mut dual = Fraction::from(0);
+                for (row, sign) in constraint_rows.iter() {
+                    dual = dual + Fraction::from(*sign) * row_duals[*row].clone();
+                }
+                dual
+            }).collect()
+        });
+        let reduced_costs = solution.reduced_costs.as_ref().map(|reduced_costs| {
+            self.variables.iter().cloned().zip(reduced_costs.iter().cloned()).collect()
+        });
+
+        Ok(ModelSolution {
+            status: solution.status.clone(),
+            objective: solution.objective.clone(),
+            values,
+            duals,
+            reduced_costs,
+            tableau_solution: solution,
+        })
+    }
+}
+
+impl Default for Model {
+    fn default() -> Model {
+        Model::new()
+    }
+}
+
+impl ModelSolution {
+    pub fn value(&self, name: &str) -> Option<&Fraction> {
+        self.values.iter().find(|(variable, _)| variable == name).map(|(_, value)| value)
+    }
+}
+
+impl From<Variable> for LinearExpression {
+    fn from(variable: Variable) -> LinearExpression {
+        LinearExpression::new().term(variable, 1f64)
+    }
+}
+
+impl ops::Mul<Variable> for f64 {
+    type Output = LinearExpression;
+
+    fn mul(self, variable: Variable) -> LinearExpression {
+        LinearExpression::new().term(variable, self)
+    }
+}
+
+impl ops::Neg for Variable {
+    type Output = LinearExpression;
+
+    fn neg(self) -> LinearExpression {
+        LinearExpression::new().term(self, -1f64)
+    }
+}
+
+impl<E: Into<LinearExpression>> ops::Add<E> for LinearExpression {
+    type Output = LinearExpression;
+
+    fn add(mut self, other: E) -> LinearExpression {
+        self.terms.extend(other.into().terms);
+        self
+    }
+}
+
+impl<E: Into<LinearExpression>> ops::Sub<E> for LinearExpression {
+    type Output = LinearExpression;
+
+    fn sub(mut self, other: E) -> LinearExpression {
+        self.terms.extend(other.into().terms.into_iter().map(|(variable, coefficient)| (variable, -coefficient)));
+        self
+    }
+}
+
+impl<E: Into<LinearExpression>> ops::Add<E> for Variable {
+    type Output = LinearExpression;
+
+    fn add(self, other: E) -> LinearExpression {
+        LinearExpression::from(self) + other
+    }
+}
+
+impl<E: Into<LinearExpression>> ops::Sub<E> for Variable {
+    type Output = LinearExpression;
+
+    fn sub(self, other: E) -> LinearExpression {
+        LinearExpression::from(self) - other
+    }
+}
+
+#[cfg(test)]
+mod model_test {
+    use super::{Model, ConstraintType};
+    use super::super::fraction::Fraction;
+    use super::super::solution::SolveStatus;
+    use super::super::error::SimplexError;
+    use super::super::options::{SolverOptions, SolveType, BigMSolveType, ObjectiveSense};
+
+    #[test]
+    fn less_than_constraints(){
+        // max 3x + 5y s.t. x <= 4, 2y <= 12, 3x + 2y <= 18
+        let mut model = Model::new();
+        let x = model.add_variable("x");
+        let y = model.add_variable("y");
+        model.set_objective(3.0*x + 5.0*y);
+        model.add_constraint(x, ConstraintType::LessThanOrEqual, 4.0);
+        model.add_constraint(2.0*y, ConstraintType::LessThanOrEqual, 12.0);
+        model.add_constraint(3.0*x + 2.0*y, ConstraintType::LessThanOrEqual, 18.0);
+        for options in [SolverOptions::new(), SolverOptions::new().solve_type(SolveType::Revised), SolverOptions::new().solve_type(SolveType::Dual)].iter() {
+            let solution = model.solve(options).unwrap();
+            assert_eq!(solution.status, SolveStatus::Optimal);
+            assert_eq!(solution.objective, Fraction::from(36));
+            assert_eq!(solution.value("x"), Some(&Fraction::from(2)));
+            assert_eq!(solution.value("y"), Some(&Fraction::from(6)));
+        }
+    }
+    #[test]
+    fn general_constraints(){
+        // max -x - y s.t. x + 2y >= 4, 3x + y >= 3, x - y >= -7, where the last constraint has a negative rhs
+        let mut model = Model::new();
+        let x = model.add_variable("x");
+        let y = model.add_variable("y");
+        model.set_objective(-x - y);
+        model.add_constraint(x + 2.0*y, ConstraintType::GreaterThanOrEqual, 4.0);
+        model.add_constraint(3.0*x + y, ConstraintType::GreaterThanOrEqual, 3.0);
+        model.add_constraint(x - y, ConstraintType::GreaterThanOrEqual, -7.0);
+        for big_M_solve_type in [BigMSolveType::TwoPhase, BigMSolveType::Detached].iter() {
+            let solution = model.solve(&SolverOptions::new().big_M_solve_type(big_M_solve_type.clone())).unwrap();
+            assert_eq!(solution.status, SolveStatus::Optimal);
+            assert_eq!(solution.objective, Fraction::new(-11,5));
+            assert_eq!(solution.values, vec![(String::from("x"), Fraction::new(2,5)), (String::from("y"), Fraction::new(9,5))]);
+        }
+
+        model.add_constraint(x + y, ConstraintType::Equal, 3.0);
+        model.add_constraint(x + x - y, ConstraintType::Equal, 0.0);
+        let solution = model.solve(&SolverOptions::new()).unwrap();
+        assert_eq!(solution.status, SolveStatus::Optimal);
+        assert_eq!(solution.objective, Fraction::from(-3));
+        assert_eq!(solution.value("x"), Some(&Fraction::from(1)));
+        assert_eq!(solution.value("y"), Some(&Fraction::from(2)));
+    }
+    #[test]
+    fn minimize(){
+        // min x + y s.t. x + 2y >= 4, 3x + y >= 3
+        let mut model = Model::new();
+        let x = model.add_variable("x");
+        let y = model.add_variable("y");
+        model.set_objective(x + y);
+        model.add_constraint(x + 2.0*y, ConstraintType::GreaterThanOrEqual, 4.0);
+        model.add_constraint(3.0*x + y, ConstraintType::GreaterThanOrEqual, 3.0);
+        let options = SolverOptions::new().objective_sense(ObjectiveSense::Minimize);
+        for options in [options.clone(), options.clone().big_M_solve_type(BigMSolveType::Detached), options.clone().solve_type(SolveType::Dual)].iter() {
+            let solution = model.solve(options).unwrap();
+            assert_eq!(solution.status, SolveStatus::Optimal);
+            assert_eq!(solution.objective, Fraction::new(11,5));
+            assert_eq!(solution.values, vec![(String::from("x"), Fraction::new(2,5)), (String::from("y"), Fraction::new(9,5))]);
+        }
+    }
+    #[test]
+    fn infeasible_model(){
+        let mut model = Model::new();
+        let x = model.add_variable("x");
+        model.set_objective(x);
+        model.add_constraint(x, ConstraintType::GreaterThanOrEqual, 5.0);
+        model.add_constraint(x, ConstraintType::LessThanOrEqual, 3.0);
+        let solution = model.solve(&SolverOptions::new()).unwrap();
+        assert_eq!(solution.status, SolveStatus::Infeasible);
+        assert_eq!(solution.value("x"), None);
+    }
+    #[test]
+    fn unknown_variable(){
+        let mut other = Model::new();
+        other.add_variable("x");
+        let y = other.add_variable("y");
+        let mut model = Model::new();
+        let x = model.add_variable("x");
+        model.set_objective(x);
+        model.add_constraint(x + y, ConstraintType::LessThanOrEqual, 3.0);
+        assert_eq!(model.solve(&SolverOptions::new()), Err(SimplexError::UnknownVariable(1)));
+    }
+    #[test]
+    fn bounded_variables(){
+        // min x + 2y - z s.t. x + y >= 5, x >= 2, z - x <= 1, 1 <= x <= 3, y >= 0 and z free
+        let mut model = Model::new();
+        let x = model.add_bounded_variable("x", 1.0, 3.0);
+        let y = model.add_variable("y");
+        let z = model.add_bounded_variable("z", f64::NEG_INFINITY, f64::INFINITY);
+        model.set_objective(x + 2.0*y - z);
+        model.add_constraint(x + y, ConstraintType::GreaterThanOrEqual, 5.0);
+        model.add_constraint(x, ConstraintType::GreaterThanOrEqual, 2.0);
+        model.add_constraint(z - x, ConstraintType::LessThanOrEqual, 1.0);
+        let options = SolverOptions::new().objective_sense(ObjectiveSense::Minimize);
+        for options in [options.clone(), options.clone().big_M_solve_type(BigMSolveType::Detached), options.clone().solve_type(SolveType::Revised)].iter() {
+            let solution = model.solve(options).unwrap();
+            assert_eq!(solution.status, SolveStatus::Optimal);
+            assert_eq!(solution.objective, Fraction::from(3));
+            assert_eq!(solution.values, vec![(String::from("x"), Fraction::from(3)), (String::from("y"), Fraction::from(2)), (String::from("z"), Fraction::from(4))]);
+        }
+    }
+    #[test]
+    fn duals(){
+        let mut model = Model::new();
+        let x = model.add_variable("x");
+        let y = model.add_variable("y");
+        model.set_objective(3.0*x + 5.0*y);
+        model.add_constraint(x, ConstraintType::LessThanOrEqual, 4.0);
+        model.add_constraint(2.0*y, ConstraintType::LessThanOrEqual, 12.0);
+        model.add_constraint(3.0*x + 2.0*y, ConstraintType::LessThanOrEqual, 18.0);
+        for options in [SolverOptions::new(), SolverOptions::new().solve_type(SolveType::Dual)].iter() {
+            let solution = model.solve(options).unwrap();
+            assert_eq!(solution.duals, Some(vec![Fraction::from(0), Fraction::new(3,2), Fraction::from(1)]));
+            assert_eq!(solution.reduced_costs, Some(vec![(String::from("x"), Fraction::from(0)), (String::from("y"), Fraction::from(0))]));
+        }
+
+        // min x + y s.t. x + 2y >= 4, 3x + y >= 3, x - y <= 7
+        let mut model = Model::new();
+        let x = model.add_variable("x");
+        let y = model.add_variable("y");
+        model.set_objective(x + y);
+        model.add_constraint(x + 2.0*y, ConstraintType::GreaterThanOrEqual, 4.0);
+        model.add_constraint(3.0*x + y, ConstraintType::GreaterThanOrEqual, 3.0);
+        model.add_constraint(y - x, ConstraintType::GreaterThanOrEqual, -7.0);
+        let options = SolverOptions::new().objective_sense(ObjectiveSense::Minimize);
+        for options in [options.clone(), options.clone().big_M_solve_type(BigMSolveType::Detached), options.clone().solve_type(SolveType::Dual)].iter() {
+            let solution = model.solve(options).unwrap();
+            assert_eq!(solution.duals, Some(vec![Fraction::new(2,5), Fraction::new(1,5), Fraction::from(0)]));
+        }
+
+        // min x s.t. -x <= -2, which the tableau multiplies by -1 and gives an artificial variable, while the dual stays that of the constraint as it was added
+        let mut model = Model::new();
+        let x = model.add_variable("x");
+        model.set_objective(x);
+        model.add_constraint(-x, ConstraintType::LessThanOrEqual, -2.0);
+        let options = SolverOptions::new().objective_sense(ObjectiveSense::Minimize);
+        for options in [options.clone(), options.clone().big_M_solve_type(BigMSolveType::Detached), options.clone().solve_type(SolveType::Revised)].iter() {
+            let solution = model.solve(options).unwrap();
+            assert_eq!(solution.objective, Fraction::from(2));
+            assert_eq!(solution.duals, Some(vec![Fraction::from(-1)]));
+        }
+    }
+}
@@ -0,0 +1,193 @@
+use std::time::Duration;
+use super::pricing::{PricingRule, CustomPricingRule};
+use super::ratio_test::{RatioTestRule, CustomRatioTestRule};
+use super::scalar::Scalar;
+
+// How the entering variable is chosen each iteration
+#[derive(Debug, Clone, PartialEq)]
+pub enum VariableSelectType {
+    // the most negative reduced cost
+    Standard,
+    // the first negative reduced cost
+    Bland,
+    // the largest reduced cost relative to the length of the edge it moves along, using reference weights that are updated after each pivot
+    SteepestEdge,
+    // an approximation of steepest edge, measuring edge lengths against a reference framework of variables that is reset every so often
+    Devex,
+    // a user supplied PricingRule, set with SolverOptions::pricing_rule
+    Custom(CustomPricingRule),
+}
+
+// How the leaving row is chosen when several rows tie in the ratio test of the primal simplex method
+#[derive(Debug, Clone, PartialEq)]
+pub enum RatioTestType {
+    // the lowest row
+    LowestRow,
+    // the row with the lowest basic variable index, which together with VariableSelectType::Bland is Bland's rule
+    LowestBasicIndex,
+    // the row with the largest pivot in absolute value
+    LargestPivot,
+    // the row whose row of B^-1 over the pivot is lexicographically smallest
+    Lexicographic,
+    // a user supplied RatioTestRule, set with SolverOptions::ratio_test_rule
+    Custom(CustomRatioTestRule),
+}
+
+// How artificial variables (a cost of +-f64::MAX in c) are handled
+#[derive(Debug, Clone, PartialEq)]
+pub enum BigMSolveType {
+    TwoPhase,
+    Detached,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ObjectiveSense {
+    Maximize,
+    Minimize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SolveType {
+    Standard,
+    Revised,
+    Dual,
+}
+
+// Settings used to build and solve a Tableau.
+// Starts out maximizing with the standard simplex method using Bland's rule to pick the entering variable and the lowest row to break ties in the ratio test,
+// with the Two Phase method for artificial variables.
+//
+// let options = SolverOptions::new()
+//     .solve_type(SolveType::Revised)
+//     .variable_select_type(VariableSelectType::Standard)
+//     .max_iterations(1000);
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolverOptions {
+    pub solve_type: SolveType,
+    pub variable_select_type: VariableSelectType,
+    pub ratio_test_type: RatioTestType,
+    pub big_M_solve_type: BigMSolveType,
+    pub objective_sense: ObjectiveSense,
+    pub debug: bool,
+    pub max_iterations: Option<usize>,
+    pub time_limit: Option<Duration>,
+    pub detect_cycles: bool,
+    pub devex_reset_iterations: usize,
+    pub primal_feasibility_tolerance: Option<f64>,
+    pub dual_feasibility_tolerance: Option<f64>,
+    pub pivot_tolerance: Option<f64>,
+}
+
+impl SolverOptions {
+    pub fn new() -> SolverOptions {
+        SolverOptions {
+            solve_type: SolveType::Standard,
+            variable_select_type: VariableSelectType::Bland,
+            ratio_test_type: RatioTestType::LowestRow,
+            big_M_solve_type: BigMSolveType::TwoPhase,
+            objective_sense: ObjectiveSense::Maximize,
+            debug: false,
+            max_iterations: None,
+            time_limit: None,
+            detect_cycles: false,
+            devex_reset_iterations: 50,
+            primal_feasibility_tolerance: None,
+            dual_feasibility_tolerance: None,
+            pivot_tolerance: None,
+        }
+    }
+
+    pub fn solve_type(mut self, solve_type: SolveType) -> SolverOptions {
+        self.solve_type = solve_type;
+        self
+    }
+
+    pub fn variable_select_type(mut self, variable_select_type: VariableSelectType) -> SolverOptions {
+        self.variable_select_type = variable_select_type;
+        self
+    }
+
+    // how many iterations VariableSelectType::Devex keeps a reference framework before starting a new one. 0 keeps the first framework for the whole solve
+    pub fn devex_reset_iterations(mut self, devex_reset_iterations: usize) -> SolverOptions {
+        self.devex_reset_iterations = devex_reset_iterations;
+        self
+    }
+
+    // chooses the entering variable with a PricingRule rather than one of the built in rules
+    // the rule is only used by a Tableau over a Scalar it was written for
+    pub fn pricing_rule<T: Scalar, R: PricingRule<T> + Send + Sync + 'static>(mut self, rule: R) -> SolverOptions {
+        self.variable_select_type = VariableSelectType::Custom(CustomPricingRule::new(rule));
+        self
+    }
+
+    pub fn ratio_test_type(mut self, ratio_test_type: RatioTestType) -> SolverOptions {
+        self.ratio_test_type = ratio_test_type;
+        self
+    }
+
+    // breaks ties in the ratio test with a RatioTestRule rather than one of the built in rules
+    pub fn ratio_test_rule<T: Scalar, R: RatioTestRule<T> + Send + Sync + 'static>(mut self, rule: R) -> SolverOptions {
+        self.ratio_test_type = RatioTestType::Custom(CustomRatioTestRule::new(rule));
+        self
+    }
+
+    pub fn big_M_solve_type(mut self, big_M_solve_type: BigMSolveType) -> SolverOptions {
+        self.big_M_solve_type = big_M_solve_type;
+        self
+    }
+
+    // artificial variables are marked the same way for either sense, and are always penalized
+    pub fn objective_sense(mut self, objective_sense: ObjectiveSense) -> SolverOptions {
+        self.objective_sense = objective_sense;
+        self
+    }
+
+    pub fn debug(mut self, debug: bool) -> SolverOptions {
+        self.debug = debug;
+        self
+    }
+
+    // the most pivots solve will perform before stopping with SolveStatus::IterationLimit
+    pub fn max_iterations(mut self, max_iterations: usize) -> SolverOptions {
+        self.max_iterations = Some(max_iterations);
+        self
+    }
+
+    // the longest solve will run before stopping with SolveStatus::TimeLimit. The time is only checked between pivots
+    pub fn time_limit(mut self, time_limit: Duration) -> SolverOptions {
+        self.time_limit = Some(time_limit);
+        self
+    }
+
+    // whether solve stops with SolveStatus::CycleDetected when it comes back to a basis it has already visited. Off by default, since with Devex
+    // or a custom rule that keeps its own state, coming back to a basis does not mean that the same pivots will follow
+    pub fn detect_cycles(mut self, detect_cycles: bool) -> SolverOptions {
+        self.detect_cycles = detect_cycles;
+        self
+    }
+
+    // how far a basic variable can be outside of its bounds and still be feasible. This also decides whether Phase 1 found a feasible solution.
+    // The tolerances default to Scalar::tolerance, and are ignored by the exact Scalars
+    pub fn primal_feasibility_tolerance(mut self, tolerance: f64) -> SolverOptions {
+        self.primal_feasibility_tolerance = Some(tolerance);
+        self
+    }
+
+    // how far a reduced cost can be on the wrong side of 0 before its variable can enter the basis
+    pub fn dual_feasibility_tolerance(mut self, tolerance: f64) -> SolverOptions {
+        self.dual_feasibility_tolerance = Some(tolerance);
+        self
+    }
+
+    // the smallest entry, in absolute value, that can be pivoted on. Smaller entries are treated as 0 by the ratio tests
+    pub fn pivot_tolerance(mut self, tolerance: f64) -> SolverOptions {
+        self.pivot_tolerance = Some(tolerance);
+        self
+    }
+}
+
+impl Default for SolverOptions {
+    fn default() -> SolverOptions {
+        SolverOptions::new()
+    }
+}
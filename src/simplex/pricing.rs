@@ -0,0 +1,143 @@
+use std::fmt;
+use std::any::Any;
+use std::sync::Arc;
+use super::error::SimplexError;
+use super::fraction::Fraction;
+use super::scalar::Scalar;
+use super::big_m::BigM;
+
+// What a PricingRule can see of the Tableau when it chooses the entering variable.
+// The reduced costs are in terms of the maximized, shifted problem that the Tableau is solving, so a variable can improve the objective
+// by increasing when its reduced cost is negative, or by decreasing when it sits at its upper bound (or is free) and its reduced cost is positive.
+// While the detached coefficient method has artificial variables in the problem, the reduced costs can have a part that multiplies M
+pub struct PricingContext<'a, T: Scalar = Fraction> {
+    reduced_costs: &'a [BigM<T>],
+    directions: &'a [i64],
+    tolerance: f64,
+    iteration: usize,
+    column: &'a dyn Fn(usize) -> Vec<T>,
+}
+
+impl<'a, T: Scalar> PricingContext<'a, T> {
+    // directions holds 1, -1 or 0 for each variable, depending on whether it can improve the objective by increasing, decreasing or not at all.
+    // tolerance is the dual feasibility tolerance the directions were found with.
+    // column returns the current tableau column B^-1 A_j of a variable, which is only computed when it is asked for
+    pub fn new(reduced_costs: &'a [BigM<T>], directions: &'a [i64], tolerance: f64, iteration: usize, column: &'a dyn Fn(usize) -> Vec<T>) -> PricingContext<'a, T> {
+        PricingContext {
+            reduced_costs,
+            directions,
+            tolerance,
+            iteration,
+            column,
+        }
+    }
+
+    pub fn number_of_variables(&self) -> usize {
+        self.reduced_costs.len()
+    }
+
+    pub fn reduced_cost(&self, col: usize) -> &BigM<T> {
+        &self.reduced_costs[col]
+    }
+
+    pub fn direction(&self, col: usize) -> i64 {
+        self.directions[col]
+    }
+
+    // how quickly the objective improves as col moves in its improving direction.
+    // Parts within the tolerance of 0 are 0, so improvements compare the same way the directions were found
+    pub fn improvement(&self, col: usize) -> BigM<T> {
+        (self.reduced_costs[col].clone() * T::from_i64(-self.directions[col])).snap(self.tolerance)
+    }
+
+    pub fn tolerance(&self) -> f64 {
+        self.tolerance
+    }
+
+    pub fn column(&self, col: usize) -> Vec<T> {
+        (self.column)(col)
+    }
+
+    // the number of pivots and bound flips performed so far
+    pub fn iteration(&self) -> usize {
+        self.iteration
+    }
+}
+
+// Chooses the entering variable each iteration of the primal simplex method.
+// Returns the index of a variable with a nonzero direction, or None when no variable can improve the objective and the tableau is optimal.
+// A rule can be written for the Scalar of one kind of Tableau, or for all of them
+pub trait PricingRule<T: Scalar = Fraction> {
+    fn choose(&self, context: &PricingContext<T>) -> Option<usize>;
+}
+
+// Bland's rule, which takes the first variable that can improve the objective
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bland;
+
+impl<T: Scalar> PricingRule<T> for Bland {
+    fn choose(&self, context: &PricingContext<T>) -> Option<usize> {
+        (0..context.number_of_variables()).find(|&col| context.direction(col) != 0)
+    }
+}
+
+// Dantzig's rule, which takes the variable that improves the objective the fastest.
+// This is the most negative reduced cost when there are no upper bounds
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Dantzig;
+
+impl<T: Scalar> PricingRule<T> for Dantzig {
+    fn choose(&self, context: &PricingContext<T>) -> Option<usize> {
+        let mut entering = None;
+        let mut best_improvement = BigM::zero();
+        for col in 0..context.number_of_variables() {
+            if context.direction(col) == 0 {
+                continue;
+            }
+            let improvement = context.improvement(col);
+            if improvement > best_improvement {
+                entering = Some(col);
+                best_improvement = improvement;
+            }
+        }
+        entering
+    }
+}
+
+// A user supplied PricingRule, held by VariableSelectType::Custom.
+// It keeps the Scalar the rule was written for, and can only be used by a Tableau over that Scalar.
+// The rule must be Send and Sync, so that SolverOptions and Tableau can be sent to other threads.
+// Two of them are only equal when they share the same rule
+#[derive(Clone)]
+pub struct CustomPricingRule(Arc<dyn Any + Send + Sync>);
+
+impl CustomPricingRule {
+    pub fn new<T: Scalar, R: PricingRule<T> + Send + Sync + 'static>(rule: R) -> CustomPricingRule {
+        let rule: Arc<dyn PricingRule<T> + Send + Sync> = Arc::new(rule);
+        CustomPricingRule(Arc::new(rule))
+    }
+
+    // whether the rule can be used by a Tableau over T
+    pub fn supports<T: Scalar>(&self) -> bool {
+        self.0.is::<Arc<dyn PricingRule<T> + Send + Sync>>()
+    }
+
+    pub fn choose<T: Scalar>(&self, context: &PricingContext<T>) -> Result<Option<usize>, SimplexError> {
+        match self.0.downcast_ref::<Arc<dyn PricingRule<T> + Send + Sync>>() {
+            Some(rule) => Ok(rule.choose(context)),
+            None => Err(SimplexError::UnsupportedRule),
+        }
+    }
+}
+
+impl PartialEq for CustomPricingRule {
+    fn eq(&self, other: &CustomPricingRule) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl fmt::Debug for CustomPricingRule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CustomPricingRule")
+    }
+}
@@ -0,0 +1,178 @@
+use std::fmt;
+use std::any::Any;
+use std::sync::Arc;
+use super::error::SimplexError;
+use super::fraction::Fraction;
+use super::scalar::Scalar;
+
+// A row that ties for the minimum ratio, and so could leave the basis
+#[derive(Debug, Clone, PartialEq)]
+pub struct RatioCandidate<T: Scalar = Fraction> {
+    pub row: usize,
+    // the index of the variable that is basic in this row
+    pub basic_variable: usize,
+    // the entry of the entering column in this row
+    pub pivot: T,
+    // whether the basic variable would leave at its upper bound rather than at 0
+    pub at_upper: bool,
+}
+
+// What a RatioTestRule can see of the Tableau when it chooses the leaving row.
+// Every candidate has the same ratio, so the rule only decides how ties are broken
+pub struct RatioTestContext<'a, T: Scalar = Fraction> {
+    ratio: &'a T,
+    candidates: &'a [RatioCandidate<T>],
+    direction: i64,
+    iteration: usize,
+    basis_inverse_row: &'a dyn Fn(usize) -> Option<Vec<T>>,
+}
+
+impl<'a, T: Scalar> RatioTestContext<'a, T> {
+    // direction is 1 when the entering variable increases, and -1 when it decreases.
+    // basis_inverse_row returns a row of B^-1, which is only computed when it is asked for, and is None when the starting basis is no longer known.
+    // With RatioTestType::Lexicographic this is B^-1 against the basis the primal simplex method started from, with the rows of basic variables that started at their upper bound negated
+    pub fn new(ratio: &'a T, candidates: &'a [RatioCandidate<T>], direction: i64, iteration: usize, basis_inverse_row: &'a dyn Fn(usize) -> Option<Vec<T>>) -> RatioTestContext<'a, T> {
+        RatioTestContext {
+            ratio,
+            candidates,
+            direction,
+            iteration,
+            basis_inverse_row,
+        }
+    }
+
+    // how far the entering variable moves before the candidates leave the basis
+    pub fn ratio(&self) -> &T {
+        self.ratio
+    }
+
+    pub fn candidates(&self) -> &[RatioCandidate<T>] {
+        self.candidates
+    }
+
+    pub fn direction(&self) -> i64 {
+        self.direction
+    }
+
+    // the number of pivots and bound flips performed so far
+    pub fn iteration(&self) -> usize {
+        self.iteration
+    }
+
+    pub fn basis_inverse_row(&self, row: usize) -> Option<Vec<T>> {
+        (self.basis_inverse_row)(row)
+    }
+}
+
+// Chooses the leaving row each iteration of the primal simplex method, out of the rows that tie for the minimum ratio.
+// Returns the row of one of the candidates
+pub trait RatioTestRule<T: Scalar = Fraction> {
+    fn choose(&self, context: &RatioTestContext<T>) -> usize;
+}
+
+// takes the candidate in the lowest row
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LowestRow;
+
+impl<T: Scalar> RatioTestRule<T> for LowestRow {
+    fn choose(&self, context: &RatioTestContext<T>) -> usize {
+        context.candidates()[0].row
+    }
+}
+
+// takes the candidate with the lowest basic variable index, which is the leaving rule of Bland's rule
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LowestBasicIndex;
+
+impl<T: Scalar> RatioTestRule<T> for LowestBasicIndex {
+    fn choose(&self, context: &RatioTestContext<T>) -> usize {
+        let mut best = &context.candidates()[0];
+        for candidate in context.candidates() {
+            if candidate.basic_variable < best.basic_variable {
+                best = candidate;
+            }
+        }
+        best.row
+    }
+}
+
+// takes the candidate with the largest pivot in absolute value, which keeps the entries of the next tableau small
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LargestPivot;
+
+impl<T: Scalar> RatioTestRule<T> for LargestPivot {
+    fn choose(&self, context: &RatioTestContext<T>) -> usize {
+        let mut best = &context.candidates()[0];
+        for candidate in context.candidates() {
+            if candidate.pivot.abs() > best.pivot.abs() {
+                best = candidate;
+            }
+        }
+        best.row
+    }
+}
+
+// takes the candidate whose row of B^-1 divided by the rate its basic variable moves at is lexicographically smallest.
+// This is the ratio test of b perturbed by (e, e^2, ..., e^m), so no two candidates ever tie and the simplex method cannot cycle, whichever pricing rule is used.
+// Falls back to the lowest row when B^-1 is not known
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Lexicographic;
+
+impl<T: Scalar> RatioTestRule<T> for Lexicographic {
+    fn choose(&self, context: &RatioTestContext<T>) -> usize {
+        let mut best: Option<(usize, Vec<T>)> = None;
+        for candidate in context.candidates() {
+            let row = match context.basis_inverse_row(candidate.row) {
+                Some(row) => row,
+                None => return LowestRow.choose(context),
+            };
+            let rate = candidate.pivot.clone() * T::from_i64(context.direction());
+            let key: Vec<T> = row.into_iter().map(|entry| entry / rate.clone()).collect();
+            let better = match &best {
+                Some((_, best_key)) => key < *best_key,
+                None => true,
+            };
+            if better {
+                best = Some((candidate.row, key));
+            }
+        }
+        best.unwrap().0
+    }
+}
+
+// A user supplied RatioTestRule, held by RatioTestType::Custom.
+// Like a CustomPricingRule, it can only be used by a Tableau over the Scalar the rule was written for, and must be Send and Sync.
+// Two of them are only equal when they share the same rule
+#[derive(Clone)]
+pub struct CustomRatioTestRule(Arc<dyn Any + Send + Sync>);
+
+impl CustomRatioTestRule {
+    pub fn new<T: Scalar, R: RatioTestRule<T> + Send + Sync + 'static>(rule: R) -> CustomRatioTestRule {
+        let rule: Arc<dyn RatioTestRule<T> + Send + Sync> = Arc::new(rule);
+        CustomRatioTestRule(Arc::new(rule))
+    }
+
+    // whether the rule can be used by a Tableau over T
+    pub fn supports<T: Scalar>(&self) -> bool {
+        self.0.is::<Arc<dyn RatioTestRule<T> + Send + Sync>>()
+    }
+
+    pub fn choose<T: Scalar>(&self, context: &RatioTestContext<T>) -> Result<usize, SimplexError> {
+        match self.0.downcast_ref::<Arc<dyn RatioTestRule<T> + Send + Sync>>() {
+            Some(rule) => Ok(rule.choose(context)),
+            None => Err(SimplexError::UnsupportedRule),
+        }
+    }
+}
+
+impl PartialEq for CustomRatioTestRule {
+    fn eq(&self, other: &CustomRatioTestRule) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl fmt::Debug for CustomRatioTestRule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CustomRatioTestRule")
+    }
+}
@@ -0,0 +1,196 @@
+use std::{fmt,ops};
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+use super::fraction::Fraction;
+use super::error::SimplexError;
+
+// The numbers a Tableau can work in: Fraction or BigRational for exact arithmetic, or f64 for speed on large problems.
+// A value within the tolerance of 0 has a sign of 0, which is how rounding errors in f64 are kept from choosing pivots.
+// The exact types never round, so they ignore the tolerance
+pub trait Scalar: Clone + fmt::Debug + fmt::Display + PartialEq + PartialOrd
+    + ops::Add<Output = Self> + ops::Sub<Output = Self> + ops::Mul<Output = Self> + ops::Div<Output = Self> + ops::Neg<Output = Self> + 'static {
+    fn zero() -> Self;
+    fn one() -> Self;
+    fn from_i64(i: i64) -> Self;
+    // reads an entry of A, b, c or the bounds, returning an error for values that cannot be represented
+    fn from_f64(f: f64) -> Result<Self, SimplexError>;
+    fn to_f64(&self) -> f64;
+    fn abs(&self) -> Self;
+    // the tolerance the Tableau uses for this type
+    fn tolerance() -> f64;
+    // -1, 0 or 1, where anything within tolerance of 0 is 0
+    fn sign(&self, tolerance: f64) -> i64;
+}
+
+impl Scalar for Fraction {
+    fn zero() -> Fraction {
+        Fraction::from(0)
+    }
+
+    fn one() -> Fraction {
+        Fraction::from(1)
+    }
+
+    fn from_i64(i: i64) -> Fraction {
+        Fraction::from(i)
+    }
+
+    fn from_f64(f: f64) -> Result<Fraction, SimplexError> {
+        Fraction::try_from_f64(f)
+    }
+
+    fn to_f64(&self) -> f64 {
+        f64::from(self.clone())
+    }
+
+    fn abs(&self) -> Fraction {
+        Fraction::abs(self)
+    }
+
+    fn tolerance() -> f64 {
+        0f64
+    }
+
+    fn sign(&self, _tolerance: f64) -> i64 {
+        if self.is_positive() {
+            1
+        } else if self.is_negative() {
+            -1
+        } else {
+            0
+        }
+    }
+}
+
+impl Scalar for BigRational {
+    fn zero() -> BigRational {
+        <BigRational as Zero>::zero()
+    }
+
+    fn one() -> BigRational {
+        <BigRational as One>::one()
+    }
+
+    fn from_i64(i: i64) -> BigRational {
+        BigRational::from_integer(BigInt::from(i))
+    }
+
+    // read exactly, the same way as a Fraction
+    fn from_f64(f: f64) -> Result<BigRational, SimplexError> {
+        Fraction::try_from_f64(f).map(BigRational::from)
+    }
+
+    fn to_f64(&self) -> f64 {
+        ToPrimitive::to_f64(self).unwrap_or(f64::NAN)
+    }
+
+    fn abs(&self) -> BigRational {
+        Signed::abs(self)
+    }
+
+    fn tolerance() -> f64 {
+        0f64
+    }
+
+    fn sign(&self, _tolerance: f64) -> i64 {
+        if self.is_positive() {
+            1
+        } else if self.is_negative() {
+            -1
+        } else {
+            0
+        }
+    }
+}
+
+impl Scalar for f64 {
+    fn zero() -> f64 {
+        0f64
+    }
+
+    fn one() -> f64 {
+        1f64
+    }
+
+    fn from_i64(i: i64) -> f64 {
+        i as f64
+    }
+
+    fn from_f64(f: f64) -> Result<f64, SimplexError> {
+        if f.is_finite() {
+            Ok(f)
+        } else {
+            Err(SimplexError::UnrepresentableValue(f))
+        }
+    }
+
+    fn to_f64(&self) -> f64 {
+        *self
+    }
+
+    fn abs(&self) -> f64 {
+        f64::abs(*self)
+    }
+
+    fn tolerance() -> f64 {
+        1e-9
+    }
+
+    fn sign(&self, tolerance: f64) -> i64 {
+        if *self > tolerance {
+            1
+        } else if *self < -tolerance {
+            -1
+        } else {
+            0
+        }
+    }
+}
+
+#[cfg(test)]
+mod scalar_test {
+    use super::Scalar;
+    use super::super::fraction::Fraction;
+    use super::super::error::SimplexError;
+    use num_bigint::BigInt;
+    use num_rational::BigRational;
+    fn field_ops<T: Scalar>() {
+        let two = T::from_i64(2);
+        let three = T::one() + two.clone();
+        assert_eq!(T::from_i64(6), two.clone() * three.clone());
+        assert_eq!(T::from_i64(-1), two.clone() - three.clone());
+        assert_eq!(T::from_f64(1.5).unwrap(), three.clone() / two.clone());
+        assert_eq!(T::from_i64(3), (-three.clone()).abs());
+        assert!(two < three);
+        assert_eq!(T::zero(), two.clone() - two.clone());
+        assert_eq!(2.5f64, T::from_f64(2.5).unwrap().to_f64());
+        assert_eq!(Err(SimplexError::UnrepresentableValue(f64::INFINITY)), T::from_f64(f64::INFINITY));
+        assert_eq!(1, two.sign(T::tolerance()));
+        assert_eq!(-1, (-two).sign(T::tolerance()));
+        assert_eq!(0, T::zero().sign(T::tolerance()));
+    }
+    #[test]
+    fn scalars() {
+        field_ops::<Fraction>();
+        field_ops::<BigRational>();
+        field_ops::<f64>();
+    }
+    #[test]
+    fn exact_values() {
+        // 0.1 is read in as the f64 closest to it, exactly
+        assert_eq!(Ok(BigRational::new(BigInt::from(3602879701896397i64), BigInt::from(36028797018963968i64))), BigRational::from_f64(0.1));
+        assert_eq!(Ok(Fraction::new(3602879701896397, 36028797018963968)), <Fraction as Scalar>::from_f64(0.1));
+        assert_eq!(Ok(BigRational::from_integer(BigInt::from(2).pow(100))), BigRational::from_f64(2f64.powi(100)));
+    }
+    #[test]
+    fn tolerance() {
+        // rounding errors in f64 have a sign of 0, while exact types see every nonzero value
+        let rounding = 0.1f64 + 0.2f64 - 0.3f64;
+        assert!(rounding != 0f64);
+        assert_eq!(0, rounding.sign(f64::tolerance()));
+        assert_eq!(1, rounding.sign(0f64));
+        assert_eq!(1, Fraction::new(1,i64::MAX).sign(1f64));
+        assert_eq!(-1, Scalar::sign(&-1e-6f64, 1e-9));
+    }
+}
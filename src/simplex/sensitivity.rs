@@ -0,0 +1,57 @@
+use super::fraction::Fraction;
+use super::scalar::Scalar;
+
+// The range that a single entry of c or b can move over before the optimal basis changes.
+// lower and upper are None when the entry can decrease or increase without limit
+#[derive(Debug, Clone, PartialEq)]
+pub struct SensitivityRange<T: Scalar = Fraction> {
+    pub value: T,
+    pub lower: Option<T>,
+    pub upper: Option<T>,
+}
+
+// The ranging report of an optimal Tableau, with a range for each entry of c and each entry of b of the original problem
+#[derive(Debug, Clone, PartialEq)]
+pub struct SensitivityReport<T: Scalar = Fraction> {
+    pub objective: Vec<SensitivityRange<T>>,
+    pub rhs: Vec<SensitivityRange<T>>,
+}
+
+impl<T: Scalar> SensitivityRange<T> {
+    // builds the range from the allowable decrease and increase of value, where None means there is no limit
+    pub fn new(value: T, decrease: Option<T>, increase: Option<T>) -> SensitivityRange<T> {
+        SensitivityRange {
+            lower: decrease.map(|decrease| value.clone() - decrease),
+            upper: increase.map(|increase| value.clone() + increase),
+            value,
+        }
+    }
+
+    fn print_row(&self, label: &str) {
+        let lower = match &self.lower {
+            Some(lower) => format!("{}", lower),
+            None => String::from("-inf"),
+        };
+        let upper = match &self.upper {
+            Some(upper) => format!("{}", upper),
+            None => String::from("inf"),
+        };
+        println!("{}\t{}\t{}\t{}", label, self.value, lower, upper);
+    }
+}
+
+impl<T: Scalar> SensitivityReport<T> {
+    pub fn print_table(&self) {
+        println!("Objective coefficient ranges:");
+        println!("c\tvalue\tlower\tupper");
+        for i in 0..self.objective.len() {
+            self.objective[i].print_row(&format!("c{}", i+1));
+        }
+        println!();
+        println!("Right hand side ranges:");
+        println!("b\tvalue\tlower\tupper");
+        for i in 0..self.rhs.len() {
+            self.rhs[i].print_row(&format!("b{}", i+1));
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use super::fraction::Fraction;
+use super::scalar::Scalar;
+use super::options::VariableSelectType;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SolveStatus {
+    Optimal,
+    Unbounded,
+    Infeasible,
+    // the solve stopped after SolverOptions::max_iterations pivots
+    IterationLimit,
+    // the solve stopped after running for SolverOptions::time_limit
+    TimeLimit,
+    // the solve came back to a basis it had already visited, so it would have cycled forever
+    CycleDetected,
+}
+
+// The result of solving a Tableau.
+// For an unbounded linear program, values and objective hold the last basic feasible solution that was visited.
+// For an infeasible linear program, values is empty.
+// When the solve is stopped early, values and objective hold the basis it stopped at. Once Phase 1 is over, this is the best basic feasible solution that was visited,
+// but during Phase 1 or the dual simplex method it may not be feasible, and values also holds any artificial variables.
+// duals and reduced_costs are only given for an optimal solution.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Solution<T: Scalar = Fraction> {
+    pub status: SolveStatus,
+    pub values: Vec<T>,
+    pub objective: T,
+    pub basis: Vec<usize>,
+    pub iterations: usize,
+    // the shadow price of each row of b
+    pub duals: Option<Vec<T>>,
+    // c_j - y * A_j for each variable
+    pub reduced_costs: Option<Vec<T>>,
+    pub statistics: SolveStatistics,
+}
+
+// Counts of what happened during a solve, for comparing the variable selection rules on the same problem
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolveStatistics {
+    pub variable_select_type: VariableSelectType,
+    // every pivot and bound flip, including those of Phase 1
+    pub iterations: usize,
+    pub phase_1_iterations: usize,
+    // pivots that did not change the value of any variable
+    pub degenerate_pivots: usize,
+    pub bound_flips: usize,
+    // how many reference frameworks Devex started, including the first one
+    pub reference_resets: usize,
+}
+
+impl SolveStatistics {
+    pub fn new(variable_select_type: VariableSelectType) -> SolveStatistics {
+        SolveStatistics {
+            variable_select_type,
+            iterations: 0,
+            phase_1_iterations: 0,
+            degenerate_pivots: 0,
+            bound_flips: 0,
+            reference_resets: 0,
+        }
+    }
+}
@@ -1,4 +1,14 @@
-use super::fraction::Fraction;
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+use super::fraction::{Fraction, ExtendedFraction};
+use super::scalar::Scalar;
+use super::big_m::BigM;
+use super::solution::{Solution, SolveStatus, SolveStatistics};
+use super::error::SimplexError;
+use super::sensitivity::{SensitivityReport, SensitivityRange};
+use super::pricing::{PricingRule, PricingContext, Bland, Dantzig};
+use super::ratio_test::{RatioTestRule, RatioTestContext, RatioCandidate, LowestRow, LowestBasicIndex, LargestPivot, Lexicographic};
+use super::options::{SolverOptions, SolveType, VariableSelectType, RatioTestType, BigMSolveType, ObjectiveSense};
 
 #[derive(Debug, Clone, PartialEq)]
 enum SolveMessage {
@@ -6,151 +16,300 @@ enum SolveMessage {
     Unbounded,
     Phase1Complete,
     Infeasible,
+    IterationLimit,
+    TimeLimit,
+    CycleDetected,
+    // a custom rule made a choice the solve cannot use, which solve returns as an error
+    Error(SimplexError),
     None,
 }
 
+// The tableau works with variables that have a lower bound of 0. Each column remembers how its variable was shifted to get there,
+// so that x = offset + x', or x = offset - x' when the variable only had an upper bound.
+// range is the upper bound of x', and free variables have no bounds at all
 #[derive(Debug, Clone, PartialEq)]
-enum VariableSelectType {
-    Standard,
-    Bland,
+struct ColumnBounds<T: Scalar> {
+    offset: T,
+    negated: bool,
+    range: Option<T>,
+    free: bool,
+    at_upper: bool,
 }
 
-#[derive(Debug, Clone, PartialEq)]
-enum BigMSolveType {
-    TwoPhase,
-    Detatched,
-}
-
-#[derive(Debug, Clone, PartialEq)]
-enum SolveType {
-    Standard,
-    Revised,
-    Dual,
+impl<T: Scalar> ColumnBounds<T> {
+    fn new() -> ColumnBounds<T> {
+        ColumnBounds {
+            offset: T::zero(),
+            negated: false,
+            range: None,
+            free: false,
+            at_upper: false,
+        }
+    }
 }
 
-
 #[derive(Debug, Clone, PartialEq)]
-pub struct Tableau {
+pub struct Tableau<T: Scalar = Fraction> {
     n: usize,
     m: usize,
 
     solve_type: SolveType,
     variable_select_type: VariableSelectType,
+    ratio_test_type: RatioTestType,
     big_M_solve_type: BigMSolveType,
     debug: bool,
+    max_iterations: Option<usize>,
+    time_limit: Option<Duration>,
+    detect_cycles: bool,
+    devex_reset_iterations: usize,
+    primal_tolerance: f64,
+    dual_tolerance: f64,
+    pivot_tolerance: f64,
+    minimize: bool,
     big_M: bool,
+    dual_phase_1: bool,
 
-    A: Vec<Vec<Fraction>>,
-    b: Vec<Fraction>,
-    c: Vec<Fraction>,
-    reduced_cost: Vec<Fraction>,
-    obj: Fraction,
+    A: Vec<Vec<T>>,
+    b: Vec<T>,
+    c: Vec<BigM<T>>,
+    original_b: Vec<T>,
+    reduced_cost: Vec<BigM<T>>,
+    obj: BigM<T>,
+    bounds: Vec<ColumnBounds<T>>,
+    objective_offset: T,
 
     basis_indecies: Vec<usize>,
-    basis_cost_vector: Vec<Fraction>,
-    two_phase_cost_vector: Vec<Fraction>,
+    basis_cost_vector: Vec<BigM<T>>,
+    two_phase_cost_vector: Vec<T>,
     original_basis_indecies: Option<Vec<usize>>,
-
-    b_inverse: Vec<Vec<Fraction>>,
-    solution: Vec<Fraction>,
+    removed_basis_columns: Vec<Option<Vec<T>>>,
+    flipped_rows: Vec<bool>,
+
+    b_inverse: Vec<Vec<T>>,
+    entering_column: Vec<T>,
+    reference_weights: Vec<T>,
+    lexicographic_rows: Vec<Vec<T>>,
+    solution: Vec<T>,
     
     entering_variable_index: usize,
     leaving_variable_index: usize,
+    entering_direction: T,
+    leaving_at_upper: bool,
+    bound_flip: bool,
 
     solved: bool, 
     additional_info: SolveMessage,
+    iterations: usize,
+    statistics: SolveStatistics,
+    started: Option<Instant>,
+    visited_bases: HashSet<(Vec<usize>, Vec<bool>)>,
 }
 
+// new and with_bounds build a Tableau that works in exact Fractions
 impl Tableau {
-    pub fn new(A: &[&[f64]], b: &[f64], c: &[f64], variable_select_type: String, solve_type: String, big_M_solve_type: String) -> Tableau {
+    pub fn new(A: &[&[f64]], b: &[f64], c: &[f64], options: &SolverOptions) -> Result<Tableau, SimplexError> {
+        Tableau::with_scalar(A, b, c, options)
+    }
+
+    // lower and upper give the bounds of each variable, using f64::NEG_INFINITY and f64::INFINITY for variables without a lower or upper bound
+    pub fn with_bounds(A: &[&[f64]], b: &[f64], c: &[f64], lower: &[f64], upper: &[f64], options: &SolverOptions) -> Result<Tableau, SimplexError> {
+        Tableau::with_scalar_bounds(A, b, c, lower, upper, options)
+    }
+}
+
+impl<T: Scalar> Tableau<T> {
+    // the same as Tableau::new, but works in any Scalar, such as Tableau::<f64>::with_scalar for fast floating point
+    pub fn with_scalar(A: &[&[f64]], b: &[f64], c: &[f64], options: &SolverOptions) -> Result<Tableau<T>, SimplexError> {
+        let lower = vec![0f64;A.len()];
+        let upper = vec![f64::INFINITY;A.len()];
+        Tableau::with_scalar_bounds(A, b, c, &lower, &upper, options)
+    }
+
+    // the same as Tableau::with_bounds, but works in any Scalar
+    pub fn with_scalar_bounds(A: &[&[f64]], b: &[f64], c: &[f64], lower: &[f64], upper: &[f64], options: &SolverOptions) -> Result<Tableau<T>, SimplexError> {
         // Checks to make sure that the dimensions of our matrices are valid.
-        // Will not need this anymore once I get the input from a website. 
-        assert_eq!(A.len(), c.len(), "A and c matrices are not compatable. c is 1x{} and A is {}x{}", c.len(), A[0].len(), A.len());
-        assert_eq!(A[0].len(), b.len(), "A and b matrices are not compatable. A is {}x{} and b is {}x1", A[0].len(), A.len(), b.len());
-        if solve_type.as_str() != "dual" {
-            for i in 0..b.len() {
-                match i%10 {
-                    0 => {assert!(b[i] >= 0f64, "{}st entry in b is negative. Linear program is not in starndard form", i+1);},
-                    1 => {assert!(b[i] >= 0f64, "{}nd entry in b is negative. Linear program is not in starndard form", i+1);},
-                    _ => {assert!(b[i] >= 0f64, "{}th entry in b is negative. Linear program is not in starndard form", i+1);},
-                }
+        if A.is_empty() || A[0].is_empty() {
+            return Err(SimplexError::EmptyProblem);
+        }
+        for col in 0..A.len() {
+            if A[col].len() != A[0].len() {
+                return Err(SimplexError::RaggedMatrix { column: col, rows: A[col].len(), expected_rows: A[0].len() });
             }
         }
+        if A.len() != c.len() {
+            return Err(SimplexError::CostDimensionMismatch { columns: A.len(), costs: c.len() });
+        }
+        if A[0].len() != b.len() {
+            return Err(SimplexError::RightHandSideDimensionMismatch { rows: A[0].len(), right_hand_sides: b.len() });
+        }
 
-        // re-work the input Strings into enum types. Potentially panics if an unknown String is passed in.
-        let solve_type_enum: SolveType;
-        let big_M_solve_type_enum: BigMSolveType;
-        let variable_select_enum: VariableSelectType;
-        match solve_type.as_str(){
-            "standard" => {solve_type_enum = SolveType::Standard;},
-            "revised" => {solve_type_enum = SolveType::Revised;},
-            "dual" => {solve_type_enum = SolveType::Dual;},
-            _ => {panic!("Not a recognized solve type.")},
+        if A.len() != lower.len() {
+            return Err(SimplexError::BoundsDimensionMismatch { columns: A.len(), bounds: lower.len() });
+        }
+        if A.len() != upper.len() {
+            return Err(SimplexError::BoundsDimensionMismatch { columns: A.len(), bounds: upper.len() });
+        }
+        for col in 0..A.len() {
+            if lower[col].is_nan() || upper[col].is_nan() || lower[col] > upper[col] || lower[col] == f64::INFINITY || upper[col] == f64::NEG_INFINITY {
+                return Err(SimplexError::InvalidBounds { column: col });
+            }
+            // artificial variables always have the default bounds
+            if c[col].abs() == f64::MAX && (lower[col] != 0f64 || upper[col] != f64::INFINITY) {
+                return Err(SimplexError::UnsupportedBounds { column: col });
+            }
+            // the dual simplex method starts from a dual feasible basis rather than a feasible one, so it has no Phase 1 to drive artificial variables out with
+            if c[col].abs() == f64::MAX && options.solve_type == SolveType::Dual {
+                return Err(SimplexError::UnsupportedArtificialVariable { column: col });
+            }
         }
-        match variable_select_type.as_str(){
-            "standard" => {variable_select_enum = VariableSelectType::Standard;},
-            "bland" => {variable_select_enum = VariableSelectType::Bland;},
-            _ => {panic!("Not a recognized variable select type.")},
+        for tolerance in [options.primal_feasibility_tolerance, options.dual_feasibility_tolerance, options.pivot_tolerance].iter().flatten() {
+            if !tolerance.is_finite() || *tolerance < 0f64 {
+                return Err(SimplexError::InvalidTolerance(*tolerance));
+            }
+        }
+        // custom rules only work on the Scalar they were written for
+        if let VariableSelectType::Custom(rule) = &options.variable_select_type {
+            if !rule.supports::<T>() {
+                return Err(SimplexError::UnsupportedRule);
+            }
         }
-        match big_M_solve_type.as_str(){
-            "detached" => {big_M_solve_type_enum = BigMSolveType::Detatched;},
-            "twophase" => {big_M_solve_type_enum = BigMSolveType::TwoPhase;},
-            _ => {panic!("Not a recognized big M solve type.")},
+        if let RatioTestType::Custom(rule) = &options.ratio_test_type {
+            if !rule.supports::<T>() {
+                return Err(SimplexError::UnsupportedRule);
+            }
         }
 
         // build the starting Tableau
         let mut t = Tableau {
             m: A[0].len(),
             n: A.len(),
-            solve_type: solve_type_enum,
-            variable_select_type: variable_select_enum,
-            big_M_solve_type: big_M_solve_type_enum,
-            debug: true,
+            solve_type: options.solve_type.clone(),
+            variable_select_type: options.variable_select_type.clone(),
+            ratio_test_type: options.ratio_test_type.clone(),
+            big_M_solve_type: options.big_M_solve_type.clone(),
+            debug: options.debug,
+            max_iterations: options.max_iterations,
+            time_limit: options.time_limit,
+            detect_cycles: options.detect_cycles,
+            devex_reset_iterations: options.devex_reset_iterations,
+            primal_tolerance: options.primal_feasibility_tolerance.unwrap_or(T::tolerance()),
+            dual_tolerance: options.dual_feasibility_tolerance.unwrap_or(T::tolerance()),
+            pivot_tolerance: options.pivot_tolerance.unwrap_or(T::tolerance()),
+            minimize: options.objective_sense == ObjectiveSense::Minimize,
             big_M: false,
+            dual_phase_1: false,
             A: Vec::with_capacity(A.len()),
             b: Vec::with_capacity(A[0].len()),
             c: Vec::with_capacity(A.len()),
+            original_b: Vec::with_capacity(A[0].len()),
             reduced_cost: Vec::with_capacity(A.len()),
-            obj: Fraction::from(0),
-            basis_indecies: vec![A.len()+1 as usize;A[0].len()],
+            obj: BigM::zero(),
+            bounds: Vec::with_capacity(A.len()),
+            objective_offset: T::zero(),
+            basis_indecies: vec![A.len()+1;A[0].len()],
             basis_cost_vector: Vec::with_capacity(A.len()),
             two_phase_cost_vector: Vec::with_capacity(A.len()),
             original_basis_indecies: None,
+            removed_basis_columns: vec![None;A[0].len()],
+            flipped_rows: vec![false;A[0].len()],
             b_inverse: Vec::with_capacity(A[0].len()),
+            entering_column: Vec::with_capacity(A[0].len()),
+            reference_weights: Vec::with_capacity(A.len()),
+            lexicographic_rows: Vec::with_capacity(A[0].len()),
             solution: Vec::with_capacity(A.len()),
             solved: false,
             additional_info: SolveMessage::None,
+            iterations: 0,
+            statistics: SolveStatistics::new(options.variable_select_type.clone()),
+            started: None,
+            visited_bases: HashSet::new(),
             entering_variable_index: A.len(),
             leaving_variable_index: A[0].len(),
+            entering_direction: T::one(),
+            leaving_at_upper: false,
+            bound_flip: false,
         };
 
-        // fill in the tableau's tables with Fraction values
+        // fill in the tableau's tables with values of T.
+        // We always solve as a maximization problem, so when minimizing we maximize -c instead.
+        // A cost of f64::MAX or -f64::MAX marks an artificial variable whatever the objective sense,
+        // and is always given the penalty -M in the maximization
         for i in 0..t.n {
             t.A.push(Vec::with_capacity(t.m));
-            if c[i] == f64::MAX {
+            if c[i].abs() == f64::MAX {
                 t.big_M = true;
-                t.c.push(Fraction::from(i64::MAX));
-            } else if c[i] == -f64::MAX {
-                t.big_M = true;
-                t.c.push(Fraction::from(-i64::MAX));
+                t.c.push(-BigM::M());
             } else {
-                t.c.push(Fraction::from(c[i]));
+                let cost = if t.minimize { -c[i] } else { c[i] };
+                t.c.push(BigM::from(T::from_f64(cost)?));
             }
-            for j in 0..t.m {
-                t.A[i].push(Fraction::from(A[i][j]));
+            for value in A[i].iter() {
+                t.A[i].push(T::from_f64(*value)?);
             }
         }
-        for i in 0..t.m {
-            t.b.push(Fraction::from(b[i]));
+        for value in b.iter() {
+            t.b.push(T::from_f64(*value)?);
             t.b_inverse.push(Vec::with_capacity(t.m));
         }
 
-        if t.debug {
-            if t.big_M {
-                println!("Working with Big M.");
+        t.original_b = t.b.clone();
+
+        // shift each variable so that its lower bound is 0. A variable with only an upper bound u is replaced by u - x,
+        // and a variable without any bounds is left free
+        for col in 0..t.n {
+            let mut bounds = ColumnBounds::new();
+            if lower[col].is_finite() {
+                bounds.offset = T::from_f64(lower[col])?;
+                if upper[col].is_finite() {
+                    bounds.range = Some(T::from_f64(upper[col])? - bounds.offset.clone());
+                }
+            } else if upper[col].is_finite() {
+                bounds.offset = T::from_f64(upper[col])?;
+                bounds.negated = true;
+            } else {
+                bounds.free = true;
+            }
+            if t.solve_type == SolveType::Dual && (bounds.range.is_some() || bounds.free) {
+                return Err(SimplexError::UnsupportedBounds { column: col });
+            }
+
+            if bounds.offset != T::zero() {
+                for row in 0..t.m {
+                    t.b[row] = t.b[row].clone() - bounds.offset.clone() * t.A[col][row].clone();
+                }
+                t.objective_offset = t.objective_offset.clone() + bounds.offset.clone() * t.cost(col);
+            }
+            if bounds.negated {
+                for row in 0..t.m {
+                    t.A[col][row] = -t.A[col][row].clone();
+                }
+                t.c[col] = -t.c[col].clone();
+            }
+            t.bounds.push(bounds);
+        }
+
+        // the primal methods need b >= 0, so each row with a negative right hand side is multiplied by -1.
+        // flipped_rows remembers these rows so that their dual values can be given for the original row.
+        // A slack column in a flipped row is no longer a column of I, so the row gets an artificial variable instead
+        if t.solve_type != SolveType::Dual {
+            for i in 0..t.m {
+                if t.b[i] < T::zero() {
+                    for col in 0..t.n {
+                        t.A[col][i] = -t.A[col][i].clone();
+                    }
+                    t.b[i] = -t.b[i].clone();
+                    t.flipped_rows[i] = true;
+                    if t.debug {
+                        println!("Multiplied row {} by -1", i+1);
+                    }
+                }
             }
         }
 
+        if t.debug && t.big_M {
+            println!("Working with Big M.");
+        }
+
         match t.solve_type {
             SolveType::Dual => {
                 t.setup_dual_tableau();
@@ -168,87 +327,100 @@ impl Tableau {
             },
         }
 
-        t
+        Ok(t)
+    }
+
+    pub fn solve(&mut self) -> Result<Solution<T>, SimplexError> {
+        self.started = Some(Instant::now());
+        self.solve_tableau();
+
+        let status = match self.additional_info {
+            SolveMessage::Optimal => SolveStatus::Optimal,
+            SolveMessage::Unbounded => SolveStatus::Unbounded,
+            SolveMessage::Infeasible => SolveStatus::Infeasible,
+            SolveMessage::IterationLimit => SolveStatus::IterationLimit,
+            SolveMessage::TimeLimit => SolveStatus::TimeLimit,
+            SolveMessage::CycleDetected => SolveStatus::CycleDetected,
+            SolveMessage::Error(ref error) => {return Err(error.clone());},
+            _ => {return Err(SimplexError::IncompleteSolve);},
+        };
+        let values = match status {
+            SolveStatus::Infeasible => Vec::new(),
+            _ => {
+                self.retrieve_solution();
+                self.solution.clone()
+            },
+        };
+
+        Ok(Solution {
+            status,
+            values,
+            objective: self.objective_value(),
+            basis: self.basis_indecies.clone(),
+            iterations: self.iterations,
+            duals: self.dual_values(),
+            reduced_costs: self.reduced_costs(),
+            statistics: SolveStatistics {
+                iterations: self.iterations,
+                ..self.statistics.clone()
+            },
+        })
     }
 
-    pub fn solve(&mut self) {
+    fn solve_tableau(&mut self) {
         if self.big_M {
-            println!("Working with Big M");
+            if self.debug {
+                println!("Working with Big M");
+            }
             match self.big_M_solve_type {
-                BigMSolveType::Detatched => {
-
+                BigMSolveType::Detached => {
+                    while !self.solved {
+                        self.print_table();
+                        self.compute_entering_variable();
+                        if self.solved {
+                            if matches!(self.additional_info, SolveMessage::Infeasible | SolveMessage::Error(_)) {
+                                self.print_solution();
+                                return;
+                            } else {
+                                // every artificial variable is now zero, so we drop them and finish solving the tableau as normal
+                                self.remove_artificial_variables();
+                                if self.debug {
+                                    println!("Artificial variables removed:");
+                                }
+                                self.solve_tableau();
+                                return;
+                            }
+                        }
+                        self.compute_leaving_variable();
+                        if self.solved {
+                            self.print_solution();
+                            return;
+                        }
+                        self.check_limits();
+                        if self.solved {
+                            self.print_solution();
+                            return;
+                        }
+                        self.update();
+                    }
                 },
                 BigMSolveType::TwoPhase => {
                     while! self.solved {
                         self.print_table();
-                        println!("Starting Phase 1:");
+                        if self.debug {
+                            println!("Starting Phase 1:");
+                        }
                         self.compute_entering_variable();
                         if self.solved {
-                            if self.additional_info == SolveMessage::Infeasible {
+                            if matches!(self.additional_info, SolveMessage::Infeasible | SolveMessage::Error(_)) {
                                 self.print_solution();
                                 break;
                             } else {
-                                self.big_M = false;
-                                self.solved = false;
-
-                                // if artificial variables are in the basis, remove them
-                                let mut new_m = self.m;
-                                for i in (0..self.m).rev() {
-                                    // check to see if the cost of any of our basis variables is equal to +-M, if it is we mark it as a leaving variable
-                                    if self.c[self.basis_indecies[i]].abs() == Fraction::from(i64::MAX) {
-                                        self.leaving_variable_index = i;
-                                        self.entering_variable_index = self.n;
-                                        // look for any non-zero pivot to replace our degenerate artificial variable
-                                        for j in 0..self.n {
-                                            // we don't want to pivot if the entering variable is the same as the leaving variable
-                                            if j == self.basis_indecies[self.leaving_variable_index] {
-                                                continue;
-                                            }
-                                            if self.A[j][self.leaving_variable_index] != Fraction::from(0) {
-                                                self.entering_variable_index = j;
-                                                break;
-                                            }
-                                        }
-                                        if self.entering_variable_index != self.n {
-                                            self.update();
-                                        } else {
-                                            // if we reached here than the only non-zero entry was the artificial variable, so we can remove the redundant constraint
-                                            for col in 0..self.n {
-                                                self.A[col].remove(self.leaving_variable_index);
-                                            }
-                                            self.b.remove(self.leaving_variable_index);
-                                            new_m -= 1;
-                                        }
-                                    }
-                                }
-                                self.m = new_m;
-
-                                let mut new_n = self.n;
-                                for col in (0..self.n).rev() {
-                                    if self.c[col].abs() == Fraction::from(i64::MAX) {
-                                        match self.original_basis_indecies.clone() {
-                                            Some(obi) => {
-                                                if obi.contains(&col) {
-                                                    self.original_basis_indecies = None;
-                                                }
-                                            },
-                                            None => (),
-                                        }
-                                        self.A.remove(col);
-                                        self.c.remove(col);
-                                        new_n -= 1;
-                                    }
+                                self.remove_artificial_variables();
+                                if self.debug {
+                                    println!("Starting Phase 2:");
                                 }
-                                self.n = new_n;
-                                
-                                // We use the current basis to compute the new basis_cost_vector and then calculate the new reduced cost.
-                                // We can now solve the new tableau from here as normal
-                                self.basis_cost_vector.drain(..);
-                                self.reduced_cost.drain(..);
-                                self.compute_basis_cost_vector();
-                                self.compute_reduced_cost();
-                                println!("Starting Phase 2:");
-                                self.solve();
+                                self.solve_tableau();
                                 return;
                             }
                         }
@@ -257,6 +429,11 @@ impl Tableau {
                             self.print_solution();
                             return;
                         }
+                        self.check_limits();
+                        if self.solved {
+                            self.print_solution();
+                            return;
+                        }
                         self.update();
                     }
                 },
@@ -276,15 +453,55 @@ impl Tableau {
                             self.print_solution();
                             return;
                         }
+                        self.check_limits();
+                        if self.solved {
+                            self.print_solution();
+                            return;
+                        }
                         self.update();
                     }
                 },
                 SolveType::Revised => {
+                    // the revised method only keeps B^-1, so we start from the identity for the current basis
+                    self.setup_revised_tableau();
+                    while !self.solved {
+                        self.compute_revised_reduced_cost();
+                        self.print_table();
+                        self.compute_entering_variable();
+                        if self.solved {
+                            self.print_solution();
+                            return;
+                        }
+                        self.compute_entering_column();
+                        self.compute_leaving_variable();
+                        if self.solved {
+                            self.print_solution();
+                            return;
+                        }
+                        self.check_limits();
+                        if self.solved {
+                            self.print_solution();
+                            return;
+                        }
+                        self.update();
+                    }
                 },
                 SolveType::Dual => {
                     while !self.solved {
                         self.print_table();
                         self.compute_leaving_variable();
+                        if self.solved && self.dual_phase_1 {
+                            // the basis is feasible, so we put back the original costs and finish solving with the primal simplex method,
+                            // which will also find out if the linear program is unbounded
+                            self.finish_dual_phase_1();
+                            if self.debug {
+                                println!("Starting dual Phase 2:");
+                            }
+                            self.solve_type = SolveType::Standard;
+                            self.solve_tableau();
+                            self.solve_type = SolveType::Dual;
+                            return;
+                        }
                         if self.solved {
                             self.print_solution();
                             return;
@@ -294,6 +511,11 @@ impl Tableau {
                             self.print_solution();
                             return;
                         }
+                        self.check_limits();
+                        if self.solved {
+                            self.print_solution();
+                            return;
+                        }
                         self.update();
                     }
                 },
@@ -302,6 +524,37 @@ impl Tableau {
     }
 
 
+    // will set solved=true if we have already performed the maximum number of pivots, if we have run out of time,
+    // or if we have already visited the current basis, which means that the simplex method is cycling
+    fn check_limits(&mut self) {
+        if let Some(max_iterations) = self.max_iterations {
+            if self.iterations >= max_iterations {
+                self.solved = true;
+                self.additional_info = SolveMessage::IterationLimit;
+                return;
+            }
+        }
+        if let (Some(time_limit), Some(started)) = (self.time_limit, self.started) {
+            if started.elapsed() >= time_limit {
+                self.solved = true;
+                self.additional_info = SolveMessage::TimeLimit;
+                return;
+            }
+        }
+        if self.detect_cycles && !self.visited_bases.insert(self.basis_key()) {
+            self.solved = true;
+            self.additional_info = SolveMessage::CycleDetected;
+        }
+    }
+
+    // the sorted basis along with which variables sit at their upper bound, since a bound flip changes the solution without changing the basis.
+    // The whole basis is kept rather than a hash of it, so two different bases can never be mistaken for each other
+    fn basis_key(&self) -> (Vec<usize>, Vec<bool>) {
+        let mut basis = self.basis_indecies.clone();
+        basis.sort_unstable();
+        (basis, self.bounds.iter().map(|bounds| bounds.at_upper).collect())
+    }
+
     pub fn set_debug(&mut self, input: bool) {
         self.debug = input;
     }
@@ -309,15 +562,15 @@ impl Tableau {
     pub fn find_b_inverse(&mut self) {
         match self.original_basis_indecies.clone() {
             Some(obi) => {
-                for i in 0..self.m {
-                    for j in 0..self.m {
-                        self.b_inverse[i].push(self.A[obi[i]][j].clone());
-                    }
+                let mut b_inverse = Vec::with_capacity(obi.len());
+                for i in 0..obi.len() {
+                    b_inverse.push(self.basis_inverse_column(i));
                 }
+                self.b_inverse = b_inverse;
                 if self.debug {
                     for i in 0..self.m {
                         print!("[\t");
-                        for j in 0..self.m {
+                        for j in 0..self.b_inverse.len() {
                             print!("{}\t", self.b_inverse[j][i]);
                         }
                         println!("]");
@@ -330,33 +583,307 @@ impl Tableau {
         }
     }
 
+    // the column of B^-1 for the ith row, which is what the ith column of our starting identity matrix has become.
+    // must only be called when original_basis_indecies is known
+    fn basis_inverse_column(&self, i: usize) -> Vec<T> {
+        let column = match &self.removed_basis_columns[i] {
+            Some(column) => column.clone(),
+            None => self.A[self.original_basis_indecies.as_ref().unwrap()[i]].clone(),
+        };
+        self.current_column(column)
+    }
+
+    // what a column of A looks like in the current tableau
+    fn current_column(&self, column: Vec<T>) -> Vec<T> {
+        if self.solve_type != SolveType::Revised || self.big_M {
+            return column;
+        }
+
+        // A is never updated by the revised method, so the column still has to be multiplied by our stored inverse
+        let mut product = Vec::with_capacity(self.m);
+        for row in 0..self.m {
+            let mut sum = T::zero();
+            for (j, entry) in column.iter().enumerate() {
+                sum = sum + self.b_inverse[j][row].clone() * entry.clone();
+            }
+            product.push(sum);
+        }
+        product
+    }
+
+    // the dual value (shadow price) of each row of the original problem, which is how quickly the objective value changes as b[i] increases.
+    // Returns None unless the tableau is optimal
+    pub fn dual_values(&self) -> Option<Vec<T>> {
+        if self.additional_info != SolveMessage::Optimal {
+            return None;
+        }
+        let obi = self.original_basis_indecies.as_ref()?;
+
+        // y = c_B * B^-1
+        let mut duals = Vec::with_capacity(obi.len());
+        for i in 0..obi.len() {
+            let column = self.basis_inverse_column(i);
+            let mut dual = T::zero();
+            for (row, entry) in column.iter().enumerate() {
+                dual = dual + self.cost(self.basis_indecies[row]) * entry.clone();
+            }
+            // undo the negation of the row, and of c when minimizing
+            if self.flipped_rows[i] != self.minimize {
+                dual = -dual;
+            }
+            duals.push(dual);
+        }
+        Some(duals)
+    }
+
+    // how far each entry of c and b of the original problem can move before the optimal basis changes.
+    // Returns None unless the tableau is optimal and the starting basis is still known
+    #[allow(clippy::needless_range_loop)]
+    pub fn sensitivity(&self) -> Option<SensitivityReport<T>> {
+        if self.additional_info != SolveMessage::Optimal {
+            return None;
+        }
+        let obi = self.original_basis_indecies.as_ref()?;
+        let zero = T::zero();
+
+        let mut basis_row = vec![None;self.n];
+        for row in 0..self.m {
+            basis_row[self.basis_indecies[row]] = Some(row);
+        }
+
+        // the ranges are first found for the c and b that the tableau works with, as the allowable decrease and increase, where None means there is no limit.
+        // The artificial variables are gone once the tableau is optimal, so no reduced cost has a part that multiplies M
+        let mut objective = Vec::with_capacity(self.n);
+        for col in 0..self.n {
+            let mut decrease = None;
+            let mut increase = None;
+            match basis_row[col] {
+                Some(row) => {
+                    // changing the cost of a basic variable by some amount changes the reduced cost of each nonbasic column k by that amount times its entry in our row.
+                    // The reduced cost must stay >= 0 for a variable at its lower bound, <= 0 for a variable at its upper bound, and 0 for a free variable
+                    for k in 0..self.n {
+                        if basis_row[k].is_some() {
+                            continue;
+                        }
+                        let entry = self.current_column(self.A[k].clone())[row].clone();
+                        if entry == zero {
+                            continue;
+                        }
+                        let limit = -self.reduced_cost[k].constant.clone() / entry.clone();
+                        if self.bounds[k].free {
+                            decrease = Some(zero.clone());
+                            increase = Some(zero.clone());
+                        } else if (entry > zero) != self.bounds[k].at_upper {
+                            decrease = smaller_limit(decrease, -limit);
+                        } else {
+                            increase = smaller_limit(increase, limit);
+                        }
+                    }
+                },
+                None => {
+                    // changing the cost of a nonbasic variable only changes its own reduced cost
+                    if self.bounds[col].free {
+                        decrease = Some(zero.clone());
+                        increase = Some(zero.clone());
+                    } else if self.bounds[col].at_upper {
+                        decrease = Some(-self.reduced_cost[col].constant.clone());
+                    } else {
+                        increase = Some(self.reduced_cost[col].constant.clone());
+                    }
+                },
+            }
+
+            // undo the negation of the column, and of c when minimizing
+            if self.bounds[col].negated != self.minimize {
+                objective.push(SensitivityRange::new(-self.cost(col), increase, decrease));
+            } else {
+                objective.push(SensitivityRange::new(self.cost(col), decrease, increase));
+            }
+        }
+
+        let mut rhs = Vec::with_capacity(obi.len());
+        for i in 0..obi.len() {
+            // changing b[i] by some amount changes each basic variable by that amount times the ith column of B^-1,
+            // and each basic variable must stay between 0 and its upper bound
+            let column = self.basis_inverse_column(i);
+            let mut decrease = None;
+            let mut increase = None;
+            for row in 0..self.m {
+                let bounds = &self.bounds[self.basis_indecies[row]];
+                if column[row] == zero || bounds.free {
+                    continue;
+                }
+                let to_zero = -self.b[row].clone() / column[row].clone();
+                if column[row] > zero {
+                    decrease = smaller_limit(decrease, -to_zero);
+                } else {
+                    increase = smaller_limit(increase, to_zero);
+                }
+                if let Some(range) = &bounds.range {
+                    let to_upper = (range.clone() - self.b[row].clone()) / column[row].clone();
+                    if column[row] > zero {
+                        increase = smaller_limit(increase, to_upper);
+                    } else {
+                        decrease = smaller_limit(decrease, -to_upper);
+                    }
+                }
+            }
+
+            if self.flipped_rows[i] {
+                rhs.push(SensitivityRange::new(self.original_b[i].clone(), increase, decrease));
+            } else {
+                rhs.push(SensitivityRange::new(self.original_b[i].clone(), decrease, increase));
+            }
+        }
+
+        Some(SensitivityReport { objective, rhs })
+    }
+
+    // the reduced cost of each variable in terms of the original problem, c_j - y * A_j, which is how quickly the objective value changes as the variable increases.
+    // Returns None unless the tableau is optimal
+    pub fn reduced_costs(&self) -> Option<Vec<T>> {
+        if self.additional_info != SolveMessage::Optimal {
+            return None;
+        }
+        let mut reduced_costs = Vec::with_capacity(self.n);
+        for col in 0..self.n {
+            // our reduced_cost row holds y * A_j - c_j for the shifted variable, which is negated again if the variable was replaced by u - x
+            if self.bounds[col].negated != self.minimize {
+                reduced_costs.push(self.reduced_cost[col].constant.clone());
+            } else {
+                reduced_costs.push(-self.reduced_cost[col].constant.clone());
+            }
+        }
+        Some(reduced_costs)
+    }
+
+    fn remove_artificial_variables(&mut self) {
+        self.solved = false;
+        self.statistics.phase_1_iterations = self.iterations;
+        // the costs change for Phase 2, so a basis from Phase 1 can be visited again without cycling
+        self.visited_bases.clear();
+        // Phase 2 starts the lexicographic ratio test again from its own basis, and the pivots below do not have to keep the rows lexicographically positive
+        self.lexicographic_rows.drain(..);
+
+        // if artificial variables are in the basis, remove them
+        let mut new_m = self.m;
+        for i in (0..self.m).rev() {
+            // check to see if the cost of any of our basis variables is equal to +-M, if it is we mark it as a leaving variable
+            if self.is_artificial(self.basis_indecies[i]) {
+                self.leaving_variable_index = i;
+                self.entering_variable_index = self.n;
+                // look for any non-zero pivot to replace our degenerate artificial variable
+                for j in 0..self.n {
+                    // we don't want to pivot if the entering variable is the same as the leaving variable, or another artificial variable
+                    if j == self.basis_indecies[self.leaving_variable_index] || self.is_artificial(j) {
+                        continue;
+                    }
+                    if self.A[j][self.leaving_variable_index].sign(self.pivot_tolerance) != 0 {
+                        self.entering_variable_index = j;
+                        break;
+                    }
+                }
+                if self.entering_variable_index != self.n {
+                    self.update();
+                } else {
+                    // if we reached here than the only non-zero entry was the artificial variable, so we can remove the redundant constraint
+                    for col in 0..self.n {
+                        self.A[col].remove(self.leaving_variable_index);
+                    }
+                    self.b.remove(self.leaving_variable_index);
+                    self.basis_indecies.remove(self.leaving_variable_index);
+                    new_m -= 1;
+                }
+            }
+        }
+        self.m = new_m;
+        self.big_M = false;
+
+        let mut new_n = self.n;
+        for col in (0..self.n).rev() {
+            if self.is_artificial(col) {
+                // the column of an artificial variable in the starting basis is still needed for B^-1, so we keep updating it on the side
+                if let Some(obi) = self.original_basis_indecies.clone() {
+                    for (i, basis_col) in obi.iter().enumerate() {
+                        if *basis_col == col && self.removed_basis_columns[i].is_none() {
+                            self.removed_basis_columns[i] = Some(self.A[col].clone());
+                        }
+                    }
+                }
+                self.A.remove(col);
+                self.c.remove(col);
+                self.bounds.remove(col);
+                new_n -= 1;
+
+                // every column after the one we removed has shifted over by one
+                for i in 0..self.m {
+                    if self.basis_indecies[i] > col {
+                        self.basis_indecies[i] -= 1;
+                    }
+                }
+                if let Some(obi) = self.original_basis_indecies.as_mut() {
+                    for basis_col in obi.iter_mut() {
+                        if *basis_col > col {
+                            *basis_col -= 1;
+                        }
+                    }
+                }
+            }
+        }
+        self.n = new_n;
+        self.reference_weights.drain(..);
+
+        // We use the current basis to compute the new basis_cost_vector and then calculate the new reduced cost.
+        // We can now solve the new tableau from here as normal
+        self.basis_cost_vector.drain(..);
+        self.reduced_cost.drain(..);
+        self.compute_basis_cost_vector();
+        self.compute_reduced_cost();
+    }
+
+    #[allow(clippy::needless_range_loop)]
     fn find_basis_indecies(&mut self) {
         // create a row of the identity matrix to match against
-        let mut I = vec![Fraction::from(0);self.m];
-        I[0] = Fraction::from(1);
+        let mut I = vec![T::zero();self.m];
+        I[0] = T::one();
 
         // loop through each column of A and see if that column matches any columns of the identity matrix.
-        // If it does match a column of the identity matrix, then the corresponding index is kept as a current basis_index and an original_basis_index
+        // If it does match a column of the identity matrix, then the corresponding index is kept as a current basis_index and an original_basis_index.
+        // A variable with an upper bound can only start in the basis if the value of b in its row is within its range
         let mut obi = vec![self.m+1;self.m];
         for A_col in 0..self.n {
             for I_col in 0..self.m {
-                if I == self.A[A_col] {
+                let fits = match &self.bounds[A_col].range {
+                    Some(range) => (self.b[I_col].clone() - range.clone()).sign(self.primal_tolerance) <= 0,
+                    None => true,
+                };
+                if fits && I == self.A[A_col] {
                     self.basis_indecies[I_col] = A_col;
                     obi[I_col] = A_col;
                 }
                 I.rotate_right(1);
             }
         }
-        self.original_basis_indecies = Some(obi);
-
-        // check that we got all the corresponding columns
+        // any row without a column of I that can start in the basis gets an artificial variable, which has a cost of -M because we always maximize.
+        // The Big M method then finds a feasible basis before solving the original problem
+        let missing = self.n+1;
         for I_col in 0..self.m {
-            match I_col%10 {
-                0 => {assert_ne!(self.basis_indecies[I_col], self.n+1, "We could not find a column in A corresponding to the {}st column of an identity matrix", I_col+1);},
-                1 => {assert_ne!(self.basis_indecies[I_col], self.n+1, "We could not find a column in A corresponding to the {}nd column of an identity matrix", I_col+1);},
-                _ => {assert_ne!(self.basis_indecies[I_col], self.n+1, "We could not find a column in A corresponding to the {}th column of an identity matrix", I_col+1);},
+            if self.basis_indecies[I_col] == missing {
+                let mut column = vec![T::zero();self.m];
+                column[I_col] = T::one();
+                self.A.push(column);
+                self.c.push(-BigM::M());
+                self.bounds.push(ColumnBounds::new());
+                self.basis_indecies[I_col] = self.n;
+                obi[I_col] = self.n;
+                self.n += 1;
+                self.big_M = true;
+                if self.debug {
+                    println!("Added an artificial variable for row {}", I_col+1);
+                }
             }
         }
+        self.original_basis_indecies = Some(obi);
 
         if self.debug {
             print!("basis indecies: [");
@@ -373,18 +900,19 @@ impl Tableau {
             
             // step 1: set up the cost vector for Phase 1
             for i in 0..self.n {
-                if self.c[i].abs() == Fraction::from(i64::MAX) {
-                    self.two_phase_cost_vector.push(Fraction::from(-1));
+                if self.is_artificial(i) {
+                    self.two_phase_cost_vector.push(-T::one());
                 } else {
-                    self.two_phase_cost_vector.push(Fraction::from(0));
+                    self.two_phase_cost_vector.push(T::zero());
                 }
             }
             // step 2: use the two_phase_cost_vector to set up the basis_cost_vector
             for i in 0..self.m {
-                self.basis_cost_vector.push(self.two_phase_cost_vector[self.basis_indecies[i]].clone());
+                self.basis_cost_vector.push(BigM::from(self.two_phase_cost_vector[self.basis_indecies[i]].clone()));
             }
 
         } else {
+            // the detached coefficient method keeps the part of each cost that multiplies M, so it needs no special case
             for i in 0..self.m {
                 self.basis_cost_vector.push(self.c[self.basis_indecies[i]].clone());
             }
@@ -405,7 +933,7 @@ impl Tableau {
         // If it is, we can skip a lot of this step
         let mut is_zero = true;
         for i in 0..self.m {
-            if self.basis_cost_vector[i] != Fraction::from(0) {
+            if !self.basis_cost_vector[i].is_zero() {
                 is_zero = false;
             }
         }
@@ -413,37 +941,43 @@ impl Tableau {
         if !is_zero {
             // add the matrix product of the cost basis and each column of A, and then subtract the original cost vector corresponding to the column we are working with
             for col in 0..self.n {
-                let mut sum = Fraction::from(0);
+                let mut sum = BigM::zero();
                 for row in 0..self.m {
-                    sum = sum + Fraction::from(self.A[col][row].clone()) * self.basis_cost_vector[row].clone();
+                    sum = sum + self.basis_cost_vector[row].clone() * self.A[col][row].clone();
                 }
                 // special case: For Phase 1 of the TwoPhase Big M method, we use the two_phase_cost_vector instead of the original cost_vector
                     // if Phase 1 is complete already, then we need to compute the reduced cost with the actual cost_vector, so it can be treated like any other tableau
                 if self.big_M && self.big_M_solve_type == BigMSolveType::TwoPhase && self.additional_info != SolveMessage::Phase1Complete {
-                    self.reduced_cost.push(Fraction::from(sum)-self.two_phase_cost_vector[col].clone());
+                    self.reduced_cost.push(sum-BigM::from(self.two_phase_cost_vector[col].clone()));
                 } else {
-                    self.reduced_cost.push(Fraction::from(sum)-self.c[col].clone());
+                    self.reduced_cost.push(sum-self.c[col].clone());
                 }
             }
 
             // set up the objective function value. There are no special cases for this
-            self.obj = Fraction::from(0);
+            self.obj = BigM::zero();
             for i in 0..self.m {
                 self.obj = self.obj.clone() + self.basis_cost_vector[i].clone() * self.b[i].clone();
             }
 
         } else {
             // if the basis_cost_vector is zero then we are able to just use the negative values of the cost_vector as the reduced cost
+            self.obj = BigM::zero();
             for col in 0..self.n {
                 //we should never end up here, as if we are introducing artificial variables then they should be used in the starting basis, but just in case we check
                 if self.big_M && self.big_M_solve_type == BigMSolveType::TwoPhase && self.additional_info != SolveMessage::Phase1Complete {
-                    self.reduced_cost.push(-self.two_phase_cost_vector[col].clone());
+                    self.reduced_cost.push(-BigM::from(self.two_phase_cost_vector[col].clone()));
                 } else {
                     self.reduced_cost.push(-self.c[col].clone());
                 }
             }
         }
 
+        // nonbasic variables at their upper bounds also add to the objective value, but only have a cost in Phase 1 if they are artificial
+        if !(self.big_M && self.big_M_solve_type == BigMSolveType::TwoPhase) {
+            self.obj = self.obj.clone() + self.upper_bound_objective();
+        }
+
         if self.debug {
             print!("Reduced cost vector: [");
             for i in 0..self.n-1 {
@@ -453,76 +987,149 @@ impl Tableau {
         }
     }
 
+    // the direction that the nonbasic variable in col can move to improve the objective: 1 to increase it, -1 to decrease it, or 0 if it can't.
+    // A negative reduced cost means the variable should increase, which it can do from its lower bound, and a positive one means it should decrease,
+    // which it can do from its upper bound. Free variables can move either way
+    fn improving_direction(&self, col: usize) -> i64 {
+        // the detached method prices on the coefficient of M first, which is how a BigM is ordered.
+        // Reduced costs within the dual feasibility tolerance are treated as 0, so rounding errors never enter the basis
+        let sign = self.reduced_cost[col].sign(self.dual_tolerance);
+        if sign < 0 && !self.bounds[col].at_upper {
+            1
+        } else if sign > 0 && (self.bounds[col].at_upper || self.bounds[col].free) {
+            -1
+        } else {
+            0
+        }
+    }
+
+    // how quickly the objective improves as col moves in the given direction
+    fn improvement_rate(&self, col: usize, direction: i64) -> BigM<T> {
+        self.reduced_cost[col].clone() * T::from_i64(-direction)
+    }
+
     // will set solved=true if the linear program is optimal
     fn compute_entering_variable(&mut self) {
         match self.solve_type {
             SolveType::Dual => {
-                let mut max_ratio = Fraction::from(-i64::MAX);
+                // the dual simplex method never has artificial variables, so the reduced costs do not have a part that multiplies M
+                let mut max_ratio = ExtendedFraction::NegInf;
                 for col in 0..self.n {
-                    if self.A[col][self.leaving_variable_index] < Fraction::from(0) && self.reduced_cost[col].clone()/self.A[col][self.leaving_variable_index].clone() > max_ratio {
-                        max_ratio = self.reduced_cost[col].clone()/self.A[col][self.leaving_variable_index].clone();
-                        self.entering_variable_index = col;
+                    // tiny pivots are never chosen
+                    if self.A[col][self.leaving_variable_index].sign(self.pivot_tolerance) < 0 {
+                        let ratio = ExtendedFraction::Finite(self.reduced_cost[col].constant.clone()/self.A[col][self.leaving_variable_index].clone());
+                        if ratio > max_ratio {
+                            max_ratio = ratio;
+                            self.entering_variable_index = col;
+                        }
                     }
                 }
                 
-                if max_ratio == Fraction::from(-i64::MAX) {
+                if max_ratio == ExtendedFraction::NegInf {
                     self.solved = true;
                     self.additional_info = SolveMessage::Infeasible;
                 }
                 
-                if self.debug {
-                    if self.debug && !self.solved {
-                        println!("Entering variable index: {:?}", self.entering_variable_index+1);
-                    }
+                if self.debug && !self.solved {
+                    println!("Entering variable index: {:?}", self.entering_variable_index+1);
                 }
             },
             _ => {
+                // usize::is_multiple_of would need a newer compiler than the rest of the crate
+                #[allow(clippy::manual_is_multiple_of)]
+                let devex_reset = self.devex_reset_iterations != 0 && self.iterations % self.devex_reset_iterations == 0;
                 match self.variable_select_type {
-                    VariableSelectType::Bland => {
-
-                        // find the first negative reduced cost and return
-                        for col in 0..self.n {
-                            if self.reduced_cost[col] < Fraction::from(0i64) {
-                                self.entering_variable_index = col;
-                                if self.debug {
-                                    println!("Entering variable index: {:?}", self.entering_variable_index+1);
-                                }
-                                return;
-                            }
-                        }
+                    VariableSelectType::SteepestEdge if self.reference_weights.len() != self.n => {
+                        self.compute_reference_weights();
                     },
-                    VariableSelectType::Standard => {
-
-                        // find the most negative reduced cost
-                        let mut most_negative_value = Fraction::from(0);
-                        self.entering_variable_index = self.n;
-                        for col in 0..self.n {
-                            if self.reduced_cost[col] < most_negative_value {
-                                self.entering_variable_index = col;
-                                most_negative_value = Fraction::from(self.reduced_cost[col].clone());
-                            }
+                    VariableSelectType::Devex if self.reference_weights.len() != self.n || devex_reset => {
+                        // start a new reference framework from the current nonbasic variables
+                        self.compute_reference_weights();
+                        self.statistics.reference_resets += 1;
+                    },
+                    _ => {},
+                }
+                let mut directions: Vec<i64> = (0..self.n).map(|col| self.improving_direction(col)).collect();
+                // the detached method improves the coefficient of M as far as it can before it looks at the constant part, the same as Phase 1 of the Two Phase method.
+                // Otherwise a variable that only improves the constant part could find an unbounded direction before the artificial variables have left the basis
+                if self.big_M && self.big_M_solve_type == BigMSolveType::Detached && (0..self.n).any(|col| directions[col] != 0 && self.reduced_cost[col].big_M.sign(self.dual_tolerance) != 0) {
+                    for (col, direction) in directions.iter_mut().enumerate() {
+                        if self.reduced_cost[col].big_M.sign(self.dual_tolerance) == 0 {
+                            *direction = 0;
                         }
-                        if self.entering_variable_index != self.n {
-                            if self.debug {
-                                println!("Entering variable index: {:?}", self.entering_variable_index+1);
+                    }
+                }
+                let entering: Result<Option<usize>, SimplexError> = match self.variable_select_type {
+                    VariableSelectType::SteepestEdge | VariableSelectType::Devex => {
+                        // take the variable with the largest improvement squared over its reference weight, which keeps the order of improvement / ||edge||
+                        let mut entering = None;
+                        let mut best_improvement = BigM::zero();
+                        for (col, direction) in directions.iter().enumerate() {
+                            if *direction == 0 {
+                                continue;
+                            }
+                            let rate = self.improvement_rate(col, *direction).snap(self.dual_tolerance);
+                            let weight = self.reference_weights[col].clone();
+                            let improvement = BigM::new(rate.constant.clone() * rate.constant.abs(), rate.big_M.clone() * rate.big_M.abs()) / weight;
+                            if improvement > best_improvement {
+                                entering = Some(col);
+                                best_improvement = improvement;
                             }
-                            return;
                         }
+                        Ok(entering)
                     },
+                    _ => {
+                        // the other rules only need what a PricingContext shows them
+                        let column = |col: usize| self.current_column(self.A[col].clone());
+                        let context = PricingContext::new(&self.reduced_cost, &directions, self.dual_tolerance, self.iterations, &column);
+                        match &self.variable_select_type {
+                            VariableSelectType::Bland => Ok(Bland.choose(&context)),
+                            VariableSelectType::Custom(rule) => rule.choose(&context),
+                            _ => Ok(Dantzig.choose(&context)),
+                        }
+                    },
+                };
+
+                // a custom rule that chooses a variable which cannot improve the objective ends the solve with an error rather than a panic
+                let entering = match entering {
+                    Ok(Some(col)) if col >= self.n || directions[col] == 0 => Err(SimplexError::InvalidEnteringVariable { column: col }),
+                    entering => entering,
+                };
+                let entering = match entering {
+                    Ok(entering) => entering,
+                    Err(error) => {
+                        self.solved = true;
+                        self.additional_info = SolveMessage::Error(error);
+                        return;
+                    },
+                };
+
+                if let Some(col) = entering {
+                    self.entering_variable_index = col;
+                    self.entering_direction = T::from_i64(directions[col]);
+
+                    if self.debug {
+                        println!("Entering variable index: {:?}", self.entering_variable_index+1);
+                    }
+                    return;
                 }
     
-                // if we could not find a negative reduced cost, then our solution is optimal
+                // if no variable can improve the objective, then our solution is optimal
                 self.solved = true;
-                if self.big_M && self.big_M_solve_type == BigMSolveType::TwoPhase && self.obj != Fraction::from(0) {
+                self.additional_info = SolveMessage::Optimal;
+                if self.big_M && self.big_M_solve_type == BigMSolveType::Detached && self.has_positive_artificial() {
+                    // if an artificial variable is still in the basis at a positive level, then there is no feasible solution
+                    self.additional_info = SolveMessage::Infeasible;
+                } else if self.big_M && self.big_M_solve_type == BigMSolveType::TwoPhase && self.obj.sign(self.primal_tolerance) != 0 {
+                    // Phase 1 ended with the artificial variables further from 0 than the primal feasibility tolerance
                     self.additional_info = SolveMessage::Infeasible;
-                } else {
-                    self.additional_info = SolveMessage::Optimal;
                 }
             },
         }
     }
 
     // will set solved=true if the linear program is unbounded
+    #[allow(clippy::needless_range_loop)]
     fn compute_leaving_variable(&mut self) {
         match self.solve_type {
             SolveType::Dual => {
@@ -535,7 +1142,7 @@ impl Tableau {
                    }
                 }
 
-                if min >= Fraction::from(0) {
+                if min.sign(self.primal_tolerance) >= 0 {
                     self.solved = true;
                     self.additional_info = SolveMessage::Optimal;
                 }
@@ -548,44 +1155,155 @@ impl Tableau {
                 }
             },
             _ => {
-                // find the minimum_ratio
+                // the revised method prices the entering column B^-1 * A_q on demand, the other methods keep it up to date in A
+                let column = if self.solve_type == SolveType::Revised && !self.big_M {
+                    self.entering_column.clone()
+                } else {
+                    self.A[self.entering_variable_index].clone()
+                };
+
+                if self.ratio_test_type == RatioTestType::Lexicographic && self.lexicographic_rows.len() != self.m {
+                    self.compute_lexicographic_rows();
+                }
+
+                // find the minimum_ratio. A basic variable stops the entering variable when it would drop below 0, or rise above its upper bound
                 self.leaving_variable_index = self.m;
-                let mut minimum_ratio = Fraction::from(i64::MAX);
+                self.leaving_at_upper = false;
+                let mut minimum_ratio = ExtendedFraction::PosInf;
+                let mut ratios = Vec::new();
                 for row in 0..self.m {
-                    if self.A[self.entering_variable_index][row] <= Fraction::from(0) {
-                        // if the entry in A[entering_variable_index] isn't positive, we don't consider it
-                        continue;
-                    }else if self.b[row].clone()/self.A[self.entering_variable_index][row].clone() < minimum_ratio {
-                        // if the current row has a smaller ratio, then we update the minimum ratio.
-                        // We use stricly less than, and we check the rows in ascending order, so that in the case of a tie, we take the first ratio we found
-                        minimum_ratio = self.b[row].clone()/self.A[self.entering_variable_index][row].clone();
-                        self.leaving_variable_index = row;
+                    // how quickly the basic variable in this row decreases as the entering variable moves
+                    let rate = self.entering_direction.clone() * column[row].clone();
+                    // entries within the pivot tolerance of 0 never stop the entering variable, so tiny pivots are never chosen
+                    let rate_sign = rate.sign(self.pivot_tolerance);
+                    let basis_bounds = self.bounds[self.basis_indecies[row]].clone();
+                    let (distance, rate, at_upper) = match basis_bounds.range {
+                        _ if rate_sign > 0 && !basis_bounds.free => (self.b[row].clone(), rate, false),
+                        Some(range) if rate_sign < 0 => (range - self.b[row].clone(), -rate, true),
+                        _ => continue,
+                    };
+                    // a basic variable that is past its bound by no more than the primal feasibility tolerance is at the bound
+                    let ratio = if distance.sign(self.primal_tolerance) <= 0 {
+                        T::zero()
+                    } else {
+                        distance/rate
+                    };
+                    if ExtendedFraction::Finite(ratio.clone()) < minimum_ratio {
+                        minimum_ratio = ExtendedFraction::Finite(ratio.clone());
+                    }
+                    ratios.push((ratio, RatioCandidate {
+                        row,
+                        basic_variable: self.basis_indecies[row],
+                        pivot: column[row].clone(),
+                        at_upper,
+                    }));
+                }
+                // every row whose ratio is within the primal feasibility tolerance of the minimum ties with it, and the ratio test rule decides which one leaves
+                if let Some(minimum) = minimum_ratio.finite() {
+                    let candidates: Vec<RatioCandidate<T>> = ratios.into_iter()
+                        .filter(|(ratio, _)| (ratio.clone() - minimum.clone()).sign(self.primal_tolerance) == 0)
+                        .map(|(_, candidate)| candidate)
+                        .collect();
+                    // a custom rule that chooses a row which does not tie ends the solve with an error rather than a panic
+                    let candidate = self.choose_leaving_row(minimum, &candidates)
+                        .and_then(|row| candidates.iter().find(|candidate| candidate.row == row).ok_or(SimplexError::InvalidLeavingRow { row }));
+                    match candidate {
+                        Ok(candidate) => {
+                            self.leaving_variable_index = candidate.row;
+                            self.leaving_at_upper = candidate.at_upper;
+                        },
+                        Err(error) => {
+                            self.solved = true;
+                            self.additional_info = SolveMessage::Error(error);
+                            return;
+                        },
                     }
                 }
 
-                if self.leaving_variable_index == self.m {
+                // if the entering variable reaches its own upper bound first, it moves to the other bound without a pivot
+                self.bound_flip = false;
+                if let Some(range) = self.bounds[self.entering_variable_index].range.clone() {
+                    let range = ExtendedFraction::Finite(range);
+                    if range <= minimum_ratio {
+                        minimum_ratio = range;
+                        self.leaving_variable_index = self.m;
+                        self.bound_flip = true;
+                    }
+                }
+
+                if self.leaving_variable_index == self.m && !self.bound_flip {
                     self.solved = true;
                     self.additional_info = SolveMessage::Unbounded;
+                    // the coefficient of M is already as good as it can be, so an artificial variable that is still positive means there is no feasible solution to be unbounded
+                    if self.big_M && self.big_M_solve_type == BigMSolveType::Detached && self.has_positive_artificial() {
+                        self.additional_info = SolveMessage::Infeasible;
+                    }
                 }
 
                 if self.debug {
-                    match self.solved {
-                        false => {println!("Minimum ratio: {}\tLeaving index: {:?}", minimum_ratio, self.leaving_variable_index+1);},
-                        true => {println!("Linear program is unbounded.");},
+                    match (self.solved, self.bound_flip) {
+                        (false, false) => {println!("Minimum ratio: {}\tLeaving index: {:?}", minimum_ratio, self.leaving_variable_index+1);},
+                        (false, true) => {println!("Minimum ratio: {}\tBound flip on index: {:?}", minimum_ratio, self.entering_variable_index+1);},
+                        (true, _) if self.additional_info == SolveMessage::Infeasible => {println!("Linear program is infeasible.");},
+                        (true, _) => {println!("Linear program is unbounded.");},
                     }
                 }
             },
         }
     }
 
+    // breaks a tie in the ratio test using our RatioTestRule
+    fn choose_leaving_row(&self, minimum_ratio: &T, candidates: &[RatioCandidate<T>]) -> Result<usize, SimplexError> {
+        if candidates.len() == 1 {
+            return Ok(candidates[0].row);
+        }
+        let basis_inverse_row = |row: usize| {
+            if self.lexicographic_rows.len() == self.m {
+                return Some(self.lexicographic_rows[row].clone());
+            }
+            let obi = self.original_basis_indecies.as_ref()?;
+            Some((0..obi.len()).map(|i| self.basis_inverse_column(i)[row].clone()).collect())
+        };
+        let context = RatioTestContext::new(minimum_ratio, candidates, self.improving_direction(self.entering_variable_index), self.iterations, &basis_inverse_row);
+        match &self.ratio_test_type {
+            RatioTestType::LowestRow => Ok(LowestRow.choose(&context)),
+            RatioTestType::LowestBasicIndex => Ok(LowestBasicIndex.choose(&context)),
+            RatioTestType::LargestPivot => Ok(LargestPivot.choose(&context)),
+            RatioTestType::Lexicographic => Ok(Lexicographic.choose(&context)),
+            RatioTestType::Custom(rule) => rule.choose(&context),
+        }
+    }
+
+    #[allow(clippy::needless_range_loop)]
     fn update(&mut self) {
         assert!(!self.solved, "Trying to update a tableau that is {:?}", self.additional_info);
+        self.iterations += 1;
+
+        if self.bound_flip {
+            self.statistics.bound_flips += 1;
+            self.flip_bound();
+            return;
+        }
+        if self.b[self.leaving_variable_index] == self.leaving_value() {
+            self.statistics.degenerate_pivots += 1;
+        }
+
+        if self.reference_weights.len() == self.n {
+            self.update_reference_weights();
+        }
+        if self.lexicographic_rows.len() == self.m {
+            self.update_lexicographic_rows();
+        }
+
+        if self.solve_type == SolveType::Revised && !self.big_M {
+            self.revised_update();
+            return;
+        }
 
         // we use temp variables to store our intermediate values
         let mut temp_A = Vec::with_capacity(self.n);
         let mut temp_b = Vec::with_capacity(self.m);
         let mut temp_r = Vec::with_capacity(self.n);
-        let temp_o: Fraction;
         for _ in 0..self.n {
             temp_A.push(Vec::with_capacity(self.m));
         }
@@ -601,13 +1319,28 @@ impl Tableau {
                 }
             }
         }
-        // update our b vector
+        // update our b vector. The entering variable moves by delta, which takes the leaving variable to 0, or to its upper bound
+        let delta = (self.b[self.leaving_variable_index].clone() - self.leaving_value()) / self.A[self.entering_variable_index][self.leaving_variable_index].clone();
         for row in 0..self.m {
             if row == self.leaving_variable_index {
                 // special case: the row corresponding to the leaving variable
-                temp_b.push(self.b[self.leaving_variable_index].clone()/self.A[self.entering_variable_index][self.leaving_variable_index].clone());
+                temp_b.push(self.entering_value() + delta.clone());
             } else {
-                temp_b.push(self.b[row].clone() - (self.b[self.leaving_variable_index].clone() * self.A[self.entering_variable_index][row].clone() / self.A[self.entering_variable_index][self.leaving_variable_index].clone()));
+                temp_b.push(self.b[row].clone() - (delta.clone() * self.A[self.entering_variable_index][row].clone()));
+            }
+        }
+        // update the columns of B^-1 that belonged to artificial variables we have already removed
+        for i in 0..self.removed_basis_columns.len() {
+            if let Some(column) = &self.removed_basis_columns[i] {
+                let mut temp_column = Vec::with_capacity(self.m);
+                for row in 0..self.m {
+                    if row == self.leaving_variable_index {
+                        temp_column.push(column[self.leaving_variable_index].clone() / self.A[self.entering_variable_index][self.leaving_variable_index].clone());
+                    } else {
+                        temp_column.push(column[row].clone() - (self.A[self.entering_variable_index][row].clone() * column[self.leaving_variable_index].clone() / self.A[self.entering_variable_index][self.leaving_variable_index].clone()));
+                    }
+                }
+                self.removed_basis_columns[i] = Some(temp_column);
             }
         }
         // update our reduced cost vector
@@ -615,9 +1348,10 @@ impl Tableau {
             temp_r.push(self.reduced_cost[col].clone() - (self.reduced_cost[self.entering_variable_index].clone() * self.A[col][self.leaving_variable_index].clone() / self.A[self.entering_variable_index][self.leaving_variable_index].clone()));
         }
         // update our objective value function
-        temp_o = self.obj.clone() - (self.b[self.leaving_variable_index].clone() * self.reduced_cost[self.entering_variable_index].clone() / self.A[self.entering_variable_index][self.leaving_variable_index].clone());
+        let temp_o = self.obj.clone() - (self.reduced_cost[self.entering_variable_index].clone() * delta);
 
         // update our basis_indecies with the entering variable in place of the leaving variable
+        self.update_bounds_status();
         self.basis_indecies[self.leaving_variable_index] = self.entering_variable_index;
         
         self.A = temp_A;
@@ -626,6 +1360,202 @@ impl Tableau {
         self.obj = temp_o;
     }
 
+    // the steepest edge weight of each column is 1 + ||B^-1 * A_j||^2, the squared length of the edge the simplex method moves along when that column enters.
+    // Devex instead starts every weight at 1, which makes the current nonbasic variables its reference framework
+    fn compute_reference_weights(&mut self) {
+        self.reference_weights.drain(..);
+        if self.variable_select_type == VariableSelectType::Devex {
+            self.reference_weights = vec![T::one();self.n];
+            return;
+        }
+        for col in 0..self.n {
+            let column = self.current_column(self.A[col].clone());
+            let mut weight = T::one();
+            for entry in column.iter() {
+                weight = weight + entry.clone() * entry.clone();
+            }
+            self.reference_weights.push(weight);
+        }
+    }
+
+    // The lexicographic ratio test solves the problem with b perturbed by (e, e^2, ..., e^m) against the basis the primal simplex method starts from,
+    // so the rows it compares are the rows of B^-1 for that starting basis, which begin as the rows of I.
+    // A basic variable sitting at its upper bound is perturbed down instead, so that every perturbed basic variable is strictly between its bounds,
+    // which means no ratio ever ties and each pivot strictly improves the perturbed objective.
+    // The rows are recomputed whenever the primal simplex method starts from a new basis, such as after Phase 1
+    fn compute_lexicographic_rows(&mut self) {
+        self.lexicographic_rows.drain(..);
+        for row in 0..self.m {
+            let mut lexicographic_row = vec![T::zero();self.m];
+            lexicographic_row[row] = match &self.bounds[self.basis_indecies[row]].range {
+                Some(range) if *range == self.b[row] && self.b[row] > T::zero() => -T::one(),
+                _ => T::one(),
+            };
+            self.lexicographic_rows.push(lexicographic_row);
+        }
+    }
+
+    // pivots the lexicographic rows along with b. must be called before the tableau is updated
+    #[allow(clippy::needless_range_loop)]
+    fn update_lexicographic_rows(&mut self) {
+        let entering_column = self.current_column(self.A[self.entering_variable_index].clone());
+        let pivot = entering_column[self.leaving_variable_index].clone();
+        let mut pivot_row = self.lexicographic_rows[self.leaving_variable_index].clone();
+        for entry in pivot_row.iter_mut() {
+            *entry = entry.clone() / pivot.clone();
+        }
+        for row in 0..self.m {
+            if row == self.leaving_variable_index {
+                self.lexicographic_rows[row] = pivot_row.clone();
+                continue;
+            }
+            for j in 0..self.m {
+                self.lexicographic_rows[row][j] = self.lexicographic_rows[row][j].clone() - entering_column[row].clone() * pivot_row[j].clone();
+            }
+        }
+    }
+
+    // updates the reference weights for the pivot we are about to do, so that we don't have to recompute every edge length.
+    // must be called before the tableau is updated
+    fn update_reference_weights(&mut self) {
+        let entering_column = self.current_column(self.A[self.entering_variable_index].clone());
+        let pivot = entering_column[self.leaving_variable_index].clone();
+        let entering_weight = self.reference_weights[self.entering_variable_index].clone();
+        let revised = self.solve_type == SolveType::Revised && !self.big_M;
+
+        // the revised method does not keep B^-1 * A, so the leaving row of it is found once from the leaving row of B^-1
+        let leaving_row: Vec<T> = if revised {
+            (0..self.m).map(|j| self.b_inverse[j][self.leaving_variable_index].clone()).collect()
+        } else {
+            Vec::new()
+        };
+        // steepest edge also needs alpha_j . alpha_q, which is A_j . (B^-T * alpha_q) in the revised method, so B^-T * alpha_q is also found once
+        let projected_column: Vec<T> = if revised && self.variable_select_type == VariableSelectType::SteepestEdge {
+            (0..self.m).map(|j| {
+                let mut sum = T::zero();
+                for (row, entry) in entering_column.iter().enumerate() {
+                    sum = sum + self.b_inverse[j][row].clone() * entry.clone();
+                }
+                sum
+            }).collect()
+        } else {
+            entering_column.clone()
+        };
+
+        for col in 0..self.n {
+            if col == self.entering_variable_index || self.basis_indecies.contains(&col) {
+                continue;
+            }
+            let entry = if revised {
+                let mut sum = T::zero();
+                for (entry, a) in leaving_row.iter().zip(self.A[col].iter()) {
+                    sum = sum + entry.clone() * a.clone();
+                }
+                sum
+            } else {
+                self.A[col][self.leaving_variable_index].clone()
+            };
+            let ratio = entry / pivot.clone();
+            if ratio == T::zero() {
+                continue;
+            }
+            if self.variable_select_type == VariableSelectType::Devex {
+                // w_j = max(w_j, ratio^2 * w_q)
+                let weight = ratio.clone() * ratio * entering_weight.clone();
+                if weight > self.reference_weights[col] {
+                    self.reference_weights[col] = weight;
+                }
+                continue;
+            }
+            let mut dot_product = T::zero();
+            for (a, entry) in self.A[col].iter().zip(projected_column.iter()) {
+                dot_product = dot_product + a.clone() * entry.clone();
+            }
+            // gamma_j = gamma_j - 2 * ratio * (alpha_j . alpha_q) + ratio^2 * gamma_q
+            self.reference_weights[col] = self.reference_weights[col].clone() - T::from_i64(2) * ratio.clone() * dot_product + ratio.clone() * ratio * entering_weight.clone();
+        }
+        // the leaving variable becomes nonbasic
+        let leaving_weight = entering_weight / (pivot.clone() * pivot);
+        if self.variable_select_type == VariableSelectType::Devex && leaving_weight < T::one() {
+            self.reference_weights[self.basis_indecies[self.leaving_variable_index]] = T::one();
+        } else {
+            self.reference_weights[self.basis_indecies[self.leaving_variable_index]] = leaving_weight;
+        }
+    }
+
+    // the value the leaving variable is moved to, which is its upper bound if the ratio test found that it rises to it and 0 otherwise
+    fn leaving_value(&self) -> T {
+        // a row that the dual method is still pivoting a starting column into has no basic variable yet
+        match self.bounds.get(self.basis_indecies[self.leaving_variable_index]).and_then(|bounds| bounds.range.as_ref()) {
+            Some(range) if self.leaving_at_upper => range.clone(),
+            _ => T::zero(),
+        }
+    }
+
+    // the value of the entering variable before it enters the basis
+    fn entering_value(&self) -> T {
+        match &self.bounds[self.entering_variable_index].range {
+            Some(range) if self.bounds[self.entering_variable_index].at_upper => range.clone(),
+            _ => T::zero(),
+        }
+    }
+
+    // must be called before the basis_indecies are updated
+    fn update_bounds_status(&mut self) {
+        if let Some(bounds) = self.bounds.get_mut(self.basis_indecies[self.leaving_variable_index]) {
+            bounds.at_upper = self.leaving_at_upper;
+        }
+        self.bounds[self.entering_variable_index].at_upper = false;
+        self.leaving_at_upper = false;
+    }
+
+    // moves the entering variable to its other bound. The basis stays the same, so only b and the objective value change
+    fn flip_bound(&mut self) {
+        self.bound_flip = false;
+        let range = match self.bounds[self.entering_variable_index].range.clone() {
+            Some(range) => range,
+            None => {return;},
+        };
+        let delta = if self.bounds[self.entering_variable_index].at_upper { -range } else { range };
+        let column = if self.solve_type == SolveType::Revised && !self.big_M {
+            self.entering_column.clone()
+        } else {
+            self.A[self.entering_variable_index].clone()
+        };
+
+        for (value, entry) in self.b.iter_mut().zip(column.iter()) {
+            *value = value.clone() - delta.clone() * entry.clone();
+        }
+        self.obj = self.obj.clone() - self.reduced_cost[self.entering_variable_index].clone() * delta;
+        self.bounds[self.entering_variable_index].at_upper = !self.bounds[self.entering_variable_index].at_upper;
+    }
+
+    // an artificial variable is marked by a cost of +-M
+    fn is_artificial(&self, col: usize) -> bool {
+        self.c[col].is_big()
+    }
+
+    // whether an artificial variable is still in the basis at a positive level
+    fn has_positive_artificial(&self) -> bool {
+        (0..self.m).any(|i| self.is_artificial(self.basis_indecies[i]) && self.b[i].sign(self.primal_tolerance) > 0)
+    }
+
+    // the constant part of the cost of col
+    fn cost(&self, col: usize) -> T {
+        self.c[col].constant.clone()
+    }
+
+    // the part of the objective value that comes from the nonbasic variables at their upper bounds
+    fn upper_bound_objective(&self) -> BigM<T> {
+        let mut sum = BigM::zero();
+        for col in 0..self.n {
+            if let (true, Some(range)) = (self.bounds[col].at_upper, &self.bounds[col].range) {
+                sum = sum + self.c[col].clone() * range.clone();
+            }
+        }
+        sum
+    }
+
     fn retrieve_solution(&mut self) {
         if self.debug {
             print!("basis: [");
@@ -635,6 +1565,7 @@ impl Tableau {
             println!("{}]", self.basis_indecies[self.m-1]);
         }
 
+        self.solution.drain(..);
         let mut in_index = false;
         for i in 0..self.n {
 
@@ -647,17 +1578,41 @@ impl Tableau {
                 }
             }
             if !in_index {
-                self.solution.push(Fraction::from(0));
+                // nonbasic variables sit at one of their bounds
+                match &self.bounds[i].range {
+                    Some(range) if self.bounds[i].at_upper => self.solution.push(range.clone()),
+                    _ => self.solution.push(T::zero()),
+                }
             }
             in_index = false;
         }
+
+        // undo the shift of each variable, so that the solution is given in terms of the original variables
+        for i in 0..self.n {
+            if self.bounds[i].negated {
+                self.solution[i] = self.bounds[i].offset.clone() - self.solution[i].clone();
+            } else {
+                self.solution[i] = self.bounds[i].offset.clone() + self.solution[i].clone();
+            }
+        }
     }
 
     fn print_table(&self) {
+        if !self.debug {
+            return;
+        }
+        // the revised method never updates A, so we show B^-1 in its place
+        let revised = self.solve_type == SolveType::Revised && !self.big_M;
         for i in 0..self.m {
             print!("[\t");
-            for j in 0..self.n {
-                print!("{}\t", self.A[j][i]);
+            if revised {
+                for j in 0..self.m {
+                    print!("{}\t", self.b_inverse[j][i]);
+                }
+            } else {
+                for j in 0..self.n {
+                    print!("{}\t", self.A[j][i]);
+                }
             }
             print!("|\t{}\t", self.b[i]);
             println!("]");
@@ -667,19 +1622,28 @@ impl Tableau {
         } 
         print!("\n[\t");
         for i in 0..self.n {
-            if self.reduced_cost[i] == Fraction::from(i64::MAX) {
-                print!("M\t");
-            } else if self.reduced_cost[i] == Fraction::from(-i64::MAX) {
-                print!("-M\t");
-            } else {
-                print!("{}\t", self.reduced_cost[i]);
-            }
+            print!("{}\t", self.reduced_cost[i]);
         }
         print!("|\t{}\t", self.obj);
         println!("]\n");
     }
 
+    // the objective value in terms of the original c, undoing the negation used for minimization
+    fn objective_value(&self) -> T {
+        // shifting the bounds of the variables moved part of the objective value into objective_offset
+        // an early stop can leave part of the objective value multiplying M, which is left out like the cost of an artificial variable
+        let obj = self.obj.constant.clone() + self.objective_offset.clone();
+        if self.minimize {
+            -obj
+        } else {
+            obj
+        }
+    }
+
     fn print_solution(&mut self) {
+        if !self.debug {
+            return;
+        }
         match self.additional_info {
             SolveMessage::Optimal => {
                 self.print_table();
@@ -690,7 +1654,7 @@ impl Tableau {
                     print!("{}, ", self.solution[i]);
                 }
                 println!("{})", self.solution[self.n-1]);
-                println!("Optimal objective function value: {}", self.obj);
+                println!("Optimal objective function value: {}", self.objective_value());
             }
             SolveMessage::Unbounded => {
                 self.print_table();
@@ -701,21 +1665,144 @@ impl Tableau {
                     print!("{}, ", self.solution[i]);
                 }
                 println!("{})", self.solution[self.n-1]);
-                println!("Objective function value: {}", self.obj);
+                println!("Objective function value: {}", self.objective_value());
             }
             SolveMessage::Infeasible => {
                 self.print_table();
                 println!("The linear program is infeasible.");
             }
+            SolveMessage::IterationLimit | SolveMessage::TimeLimit | SolveMessage::CycleDetected => {
+                self.print_table();
+                self.retrieve_solution();
+                match self.additional_info {
+                    SolveMessage::IterationLimit => println!("The solve stopped after {} iterations.", self.iterations),
+                    SolveMessage::TimeLimit => println!("The solve ran out of time after {} iterations.", self.iterations),
+                    _ => println!("The solve came back to a basis it had already visited after {} iterations.", self.iterations),
+                }
+                print!("Solution: (");
+                for i in 0..self.n-1 {
+                    print!("{}, ", self.solution[i]);
+                }
+                println!("{})", self.solution[self.n-1]);
+                println!("Objective function value: {}", self.objective_value());
+            }
             _ => {println!("An error seems to have occured.");}
         }
     }
 
+    fn setup_revised_tableau(&mut self) {
+        // the columns of our current basis form I in A, so B^-1 starts out as the identity
+        self.b_inverse = vec![vec![T::zero();self.m];self.m];
+        for i in 0..self.m {
+            self.b_inverse[i][i] = T::one();
+        }
+
+        self.basis_cost_vector.drain(..);
+        for i in 0..self.m {
+            self.basis_cost_vector.push(self.c[self.basis_indecies[i]].clone());
+        }
+
+        self.obj = self.upper_bound_objective();
+        for i in 0..self.m {
+            self.obj = self.obj.clone() + self.basis_cost_vector[i].clone() * self.b[i].clone();
+        }
+    }
+
+    fn compute_revised_reduced_cost(&mut self) {
+        // y = c_B * B^-1
+        let mut y = Vec::with_capacity(self.m);
+        for j in 0..self.m {
+            let mut sum = BigM::zero();
+            for i in 0..self.m {
+                sum = sum + self.basis_cost_vector[i].clone() * self.b_inverse[j][i].clone();
+            }
+            y.push(sum);
+        }
+
+        // reduced cost of each column is y * A_j - c_j, using the original A
+        self.reduced_cost.drain(..);
+        for col in 0..self.n {
+            let mut sum = BigM::zero();
+            for (dual, a) in y.iter().zip(self.A[col].iter()) {
+                sum = sum + dual.clone() * a.clone();
+            }
+            self.reduced_cost.push(sum - self.c[col].clone());
+        }
+
+        if self.debug {
+            print!("Reduced cost vector: [");
+            for i in 0..self.n-1 {
+                print!("{} ", self.reduced_cost[i]);
+            }
+            println!("{}]", self.reduced_cost[self.n-1]);
+        }
+    }
+
+    fn compute_entering_column(&mut self) {
+        // B^-1 * A_q for the entering column q
+        self.entering_column.drain(..);
+        for row in 0..self.m {
+            let mut sum = T::zero();
+            for j in 0..self.m {
+                sum = sum + self.b_inverse[j][row].clone() * self.A[self.entering_variable_index][j].clone();
+            }
+            self.entering_column.push(sum);
+        }
+    }
+
+    fn revised_update(&mut self) {
+        let pivot = self.entering_column[self.leaving_variable_index].clone();
+
+        // update B^-1 with the same row operations the full tableau would use
+        for col in 0..self.m {
+            let leaving_row_entry = self.b_inverse[col][self.leaving_variable_index].clone() / pivot.clone();
+            for row in 0..self.m {
+                if row == self.leaving_variable_index {
+                    self.b_inverse[col][row] = leaving_row_entry.clone();
+                } else {
+                    self.b_inverse[col][row] = self.b_inverse[col][row].clone() - self.entering_column[row].clone() * leaving_row_entry.clone();
+                }
+            }
+        }
+
+        // update the values of our basic variables, moving the entering variable by delta
+        let delta = (self.b[self.leaving_variable_index].clone() - self.leaving_value()) / pivot;
+        let entering_value = self.entering_value();
+        for row in 0..self.m {
+            if row == self.leaving_variable_index {
+                self.b[row] = entering_value.clone() + delta.clone();
+            } else {
+                self.b[row] = self.b[row].clone() - self.entering_column[row].clone() * delta.clone();
+            }
+        }
+
+        self.obj = self.obj.clone() - self.reduced_cost[self.entering_variable_index].clone() * delta;
+
+        self.update_bounds_status();
+        self.basis_indecies[self.leaving_variable_index] = self.entering_variable_index;
+        self.basis_cost_vector[self.leaving_variable_index] = self.c[self.entering_variable_index].clone();
+    }
+
+    fn finish_dual_phase_1(&mut self) {
+        self.statistics.phase_1_iterations = self.iterations;
+        self.visited_bases.clear();
+        self.dual_phase_1 = false;
+        self.solved = false;
+        self.additional_info = SolveMessage::None;
+
+        // recompute the reduced costs for the current basis with the original costs
+        self.basis_cost_vector.drain(..);
+        self.reduced_cost.drain(..);
+        self.compute_basis_cost_vector();
+        self.compute_reduced_cost();
+    }
+
+    #[allow(clippy::needless_range_loop)]
     fn setup_dual_tableau(&mut self) {
         // keep track of which columns of I we have seen, because we don't need to do any pivots on these columns
         let mut seen = vec![false; self.m];
-        let mut I = vec![Fraction::from(0);self.m];
-        I[0] = Fraction::from(1);
+        let mut I = vec![T::zero();self.m];
+        I[0] = T::one();
         for col in 0..self.n {
             for i in 0..self.m {
                 if I == self.A[col] {
@@ -725,17 +1812,18 @@ impl Tableau {
                     continue;
                 }
                 // multiply I by -1, if we have a column corresponding to -I, we can multiply the whole row by -1 to get a column of I
-                I[i] = I[i].clone() * Fraction::from(-1);
+                I[i] = I[i].clone() * -T::one();
                 if I == self.A[col] {
                     seen[i] = true;
                     self.basis_indecies[i] = col;
                     // multiply the whole row by -1
                     for c in 0..self.n {
-                        self.A[c][i] = self.A[c][i].clone() * Fraction::from(-1);
+                        self.A[c][i] = self.A[c][i].clone() * -T::one();
                     }
-                    self.b[i] = self.b[i].clone() * Fraction::from(-1);
+                    self.b[i] = self.b[i].clone() * -T::one();
+                    self.flipped_rows[i] = !self.flipped_rows[i];
                 }
-                I[i] = I[i].clone() * Fraction::from(-1);
+                I[i] = I[i].clone() * -T::one();
                 I.rotate_right(1);
             }
         }
@@ -756,9 +1844,24 @@ impl Tableau {
             }
         }
 
-        // calculate our reduced cost row, using a cost basis of zeros.
-        for _ in 0..self.n {
-            self.basis_cost_vector.push(Fraction::from(0));
+        // the columns of I we found hold B^-1 as we pivot. Each column of I that was not found is kept on the side instead,
+        // the same as the column of an artificial variable that has been removed, so that B^-1 is still known for every row
+        for i in 0..self.m {
+            if !seen[i] {
+                let mut column = vec![T::zero();self.m];
+                column[i] = T::one();
+                self.removed_basis_columns[i] = Some(column);
+            }
+        }
+        self.original_basis_indecies = Some(self.basis_indecies.clone());
+
+        // calculate our reduced cost row, using the costs of the columns of I we found, and 0 for the rows we still have to pivot on
+        for i in 0..self.m {
+            if seen[i] {
+                self.basis_cost_vector.push(self.c[self.basis_indecies[i]].clone());
+            } else {
+                self.basis_cost_vector.push(BigM::zero());
+            }
         }
         self.compute_reduced_cost();
 
@@ -770,7 +1873,7 @@ impl Tableau {
             }
             // look for the first non-zero entry and pivot
             for col in 0..self.n {
-                if self.A[col][row] != Fraction::from(0) {
+                if self.A[col][row].sign(self.pivot_tolerance) != 0 {
                     non_zero_entry_found = true;
                     self.leaving_variable_index = row;
                     self.entering_variable_index = col;
@@ -783,7 +1886,7 @@ impl Tableau {
             }
             // if we didn't find a non-zero entry, the problem is either infeasible, or the constraint is redundant
             if !non_zero_entry_found {
-                if self.b[row] != Fraction::from(0) {
+                if self.b[row].sign(self.primal_tolerance) != 0 {
                     self.solved = true;
                     self.additional_info = SolveMessage::Infeasible;
                     return;
@@ -792,6 +1895,11 @@ impl Tableau {
                     for c in 0..self.n {
                         self.A[c].remove(row);
                     }
+                    for column in self.removed_basis_columns.iter_mut().flatten() {
+                        column.remove(row);
+                    }
+                    self.b.remove(row);
+                    self.basis_indecies.remove(row);
                     self.m -= 1;
                 }
             }
@@ -820,7 +1928,7 @@ impl Tableau {
         // if we don't have negative reduced costs, we are done
         let mut negative_reduced_cost = false;
         for col in 0..self.n {
-            if self.reduced_cost[col] < Fraction::from(0) {
+            if self.reduced_cost[col].sign(self.dual_tolerance) < 0 {
                 negative_reduced_cost = true;
                 break;
             }
@@ -829,45 +1937,952 @@ impl Tableau {
             return;
         }
 
-        // Add an artificial constraint
+        // the starting basis is not dual feasible, so Phase 1 raises the cost of every column with a negative reduced cost until its reduced cost is 0.
+        // The dual simplex method then finds a feasible basis for this auxiliary problem, and Phase 2 puts the original costs back
+        self.dual_phase_1 = true;
         for col in 0..self.n {
-            if self.basis_indecies.contains(&col) {
-                self.A[col].push(Fraction::from(0));
-            } else {
-                self.A[col].push(Fraction::from(1));
+            if self.reduced_cost[col].sign(self.dual_tolerance) < 0 {
+                self.reduced_cost[col] = BigM::zero();
             }
         }
-        self.b.push(Fraction::from(1000));
-        self.m += 1;
-
-        // Add an artificial variable for the artificial constraint
-        self.A.push(vec![Fraction::from(0);self.m]);
-        self.A[self.n][self.m-1] = Fraction::from(1);
-        self.reduced_cost.push(Fraction::from(0));
-        self.c.push(Fraction::from(0));
-        self.n += 1;
-        
         if self.debug {
-            self.print_table();
+            println!("Starting dual Phase 1:");
         }
+    }
 
-        // perform a pivot on the new constraint, selecting the most negative reduced cost
-        let mut most_negative_value = Fraction::from(0);
-        self.entering_variable_index = self.n;
-        for col in 0..self.n {
-            if self.reduced_cost[col] < most_negative_value {
-                if self.debug {
-                    println!("New most negative reduced cost is {} at index {}", self.reduced_cost[col], col);
+}
+
+// the tighter of an existing limit and a new one, where None means there is no limit
+fn smaller_limit<T: Scalar>(current: Option<T>, limit: T) -> Option<T> {
+    match current {
+        Some(current) if current <= limit => Some(current),
+        _ => Some(limit),
+    }
+}
+
+#[cfg(test)]
+mod tableau_test {
+    use super::{Tableau, SolveMessage, SolveStatus, SimplexError, SolverOptions, SolveType, VariableSelectType, RatioTestType, BigMSolveType, ObjectiveSense, SensitivityRange, BigM};
+    use super::super::fraction::Fraction;
+
+    fn solve(A: &[Vec<f64>], b: &[f64], c: &[f64], options: SolverOptions) -> Tableau {
+        let A: Vec<&[f64]> = A.iter().map(|col| col.as_slice()).collect();
+        let mut t = Tableau::new(&A, b, c, &options).unwrap();
+        t.solve().unwrap();
+        t
+    }
+
+    #[test]
+    fn revised_matches_standard(){
+        // max 3x + 5y s.t. x <= 4, 2y <= 12, 3x + 2y <= 18
+        let A = vec![vec![1f64,0f64,3f64], vec![0f64,2f64,2f64], vec![1f64,0f64,0f64], vec![0f64,1f64,0f64], vec![0f64,0f64,1f64]];
+        let b = vec![4f64,12f64,18f64];
+        let c = vec![3f64,5f64,0f64,0f64,0f64];
+        for variable_select_type in [VariableSelectType::Standard, VariableSelectType::Bland].iter() {
+            let standard = solve(&A, &b, &c, SolverOptions::new().variable_select_type(variable_select_type.clone()).solve_type(SolveType::Standard));
+            let revised = solve(&A, &b, &c, SolverOptions::new().variable_select_type(variable_select_type.clone()).solve_type(SolveType::Revised));
+            assert_eq!(revised.additional_info, SolveMessage::Optimal);
+            assert_eq!(revised.obj, BigM::from(Fraction::from(36)));
+            assert_eq!(revised.obj, standard.obj, "Failed matching objective with {:?} pricing.", variable_select_type);
+            assert_eq!(revised.solution, standard.solution, "Failed matching solution with {:?} pricing.", variable_select_type);
+        }
+    }
+    #[test]
+    fn revised_degenerate(){
+        let A = vec![vec![0.25f64,0.5f64,0f64], vec![-8f64,-0.5f64,0f64], vec![-1f64,-0.5f64,1f64], vec![9f64,3f64,0f64], vec![1f64,0f64,0f64], vec![0f64,1f64,0f64], vec![0f64,0f64,1f64]];
+        let b = vec![0f64,0f64,1f64];
+        let c = vec![0.75f64,-20f64,0.5f64,-6f64,0f64,0f64,0f64];
+        let standard = solve(&A, &b, &c, SolverOptions::new().variable_select_type(VariableSelectType::Bland).solve_type(SolveType::Standard));
+        let mut revised = solve(&A, &b, &c, SolverOptions::new().variable_select_type(VariableSelectType::Bland).solve_type(SolveType::Revised));
+        assert_eq!(revised.obj, BigM::from(Fraction::new(5,4)));
+        assert_eq!(revised.solution, standard.solution);
+        revised.find_b_inverse();
+        assert_eq!(revised.b_inverse[1], vec![Fraction::from(2), Fraction::from(0), Fraction::new(-1,2)]);
+    }
+    #[test]
+    fn revised_unbounded(){
+        // max x + y s.t. x - y <= 1
+        let A = vec![vec![1f64], vec![-1f64], vec![1f64]];
+        let t = solve(&A, &[1f64], &[1f64,1f64,0f64], SolverOptions::new().variable_select_type(VariableSelectType::Standard).solve_type(SolveType::Revised));
+        assert_eq!(t.additional_info, SolveMessage::Unbounded);
+    }
+    #[test]
+    fn revised_after_two_phase(){
+        // max 2x + 3y s.t. x + y = 4, x + 3y <= 6, with an artificial variable for the equality
+        let A = vec![vec![1f64,1f64], vec![1f64,3f64], vec![0f64,1f64], vec![1f64,0f64]];
+        let b = vec![4f64,6f64];
+        let c = vec![2f64,3f64,0f64,-f64::MAX];
+        let standard = solve(&A, &b, &c, SolverOptions::new().variable_select_type(VariableSelectType::Bland).solve_type(SolveType::Standard));
+        let revised = solve(&A, &b, &c, SolverOptions::new().variable_select_type(VariableSelectType::Bland).solve_type(SolveType::Revised));
+        assert_eq!(revised.additional_info, SolveMessage::Optimal);
+        assert_eq!(revised.obj, BigM::from(Fraction::from(9)));
+        assert_eq!(revised.obj, standard.obj);
+        assert_eq!(revised.solution, standard.solution);
+    }
+    #[test]
+    fn detached_matches_two_phase(){
+        // max 2x + 3y s.t. x + y = 4, x + 3y <= 6
+        let A = vec![vec![1f64,1f64], vec![1f64,3f64], vec![0f64,1f64], vec![1f64,0f64]];
+        let b = vec![4f64,6f64];
+        let c = vec![2f64,3f64,0f64,-f64::MAX];
+        for variable_select_type in [VariableSelectType::Standard, VariableSelectType::Bland].iter() {
+            let two_phase = solve(&A, &b, &c, SolverOptions::new().variable_select_type(variable_select_type.clone()).solve_type(SolveType::Standard).big_M_solve_type(BigMSolveType::TwoPhase));
+            let detached = solve(&A, &b, &c, SolverOptions::new().variable_select_type(variable_select_type.clone()).solve_type(SolveType::Standard).big_M_solve_type(BigMSolveType::Detached));
+            assert_eq!(detached.additional_info, SolveMessage::Optimal);
+            assert_eq!(detached.obj, BigM::from(Fraction::from(9)));
+            assert_eq!(detached.obj, two_phase.obj);
+            assert_eq!(detached.solution, two_phase.solution);
+        }
+    }
+    #[test]
+    fn detached_reduced_costs(){
+        // the reduced costs and objective value keep the part that multiplies M
+        let A = [vec![1f64,1f64], vec![1f64,3f64], vec![0f64,1f64], vec![1f64,0f64]];
+        let b = vec![4f64,6f64];
+        let c = vec![2f64,3f64,0f64,-f64::MAX];
+        let A: Vec<&[f64]> = A.iter().map(|col| col.as_slice()).collect();
+        let mut detached = Tableau::new(&A, &b, &c, &SolverOptions::new().big_M_solve_type(BigMSolveType::Detached)).unwrap();
+        let reduced_costs: Vec<String> = detached.reduced_cost.iter().map(|cost| format!("{}", cost)).collect();
+        assert_eq!(reduced_costs, vec!["-2 - M", "-3 - M", "0", "0"]);
+        assert_eq!(format!("{}", detached.obj), "-4M");
+        assert_eq!(detached.c[3], -BigM::M());
+        detached.solve().unwrap();
+        assert_eq!(detached.obj, BigM::from(9));
+    }
+    #[test]
+    fn detached_surplus_constraints(){
+        // max -x - y s.t. x + 2y >= 4, 3x + y >= 3, written with surplus and artificial variables placed in between
+        let A = vec![vec![1f64,3f64], vec![2f64,1f64], vec![-1f64,0f64], vec![1f64,0f64], vec![0f64,-1f64], vec![0f64,1f64]];
+        let b = vec![4f64,3f64];
+        let c = vec![-1f64,-1f64,0f64,-f64::MAX,0f64,-f64::MAX];
+        let two_phase = solve(&A, &b, &c, SolverOptions::new().variable_select_type(VariableSelectType::Standard).solve_type(SolveType::Standard).big_M_solve_type(BigMSolveType::TwoPhase));
+        let detached = solve(&A, &b, &c, SolverOptions::new().variable_select_type(VariableSelectType::Standard).solve_type(SolveType::Standard).big_M_solve_type(BigMSolveType::Detached));
+        assert_eq!(detached.additional_info, SolveMessage::Optimal);
+        assert_eq!(detached.obj, BigM::from(Fraction::new(-11,5)));
+        assert_eq!(detached.obj, two_phase.obj);
+        assert_eq!(detached.solution, vec![Fraction::new(2,5), Fraction::new(9,5), Fraction::from(0), Fraction::from(0)]);
+        assert_eq!(detached.solution, two_phase.solution);
+    }
+    #[test]
+    fn detached_infeasible(){
+        // x + y = 4 and x + y <= 2 cannot both hold
+        let A = vec![vec![1f64,1f64], vec![1f64,1f64], vec![1f64,0f64], vec![0f64,1f64]];
+        let b = vec![4f64,2f64];
+        let c = vec![1f64,1f64,-f64::MAX,0f64];
+        let detached = solve(&A, &b, &c, SolverOptions::new().variable_select_type(VariableSelectType::Bland).solve_type(SolveType::Standard).big_M_solve_type(BigMSolveType::Detached));
+        assert_eq!(detached.additional_info, SolveMessage::Infeasible);
+        let two_phase = solve(&A, &b, &c, SolverOptions::new().variable_select_type(VariableSelectType::Bland).solve_type(SolveType::Standard).big_M_solve_type(BigMSolveType::TwoPhase));
+        assert_eq!(two_phase.additional_info, SolveMessage::Infeasible);
+    }
+    #[test]
+    fn detached_infeasible_with_unbounded_direction(){
+        // max x s.t. -2y = 1 and -3x + s = 0, where x can grow forever but the first row can never hold
+        let A = vec![vec![0f64,-3f64], vec![-2f64,0f64], vec![0f64,1f64]];
+        let b = vec![1f64,0f64];
+        let c = vec![1f64,0f64,0f64];
+        for variable_select_type in [VariableSelectType::Bland, VariableSelectType::Standard, VariableSelectType::SteepestEdge, VariableSelectType::Devex].iter() {
+            for big_M_solve_type in [BigMSolveType::Detached, BigMSolveType::TwoPhase].iter() {
+                let t = solve(&A, &b, &c, SolverOptions::new().variable_select_type(variable_select_type.clone()).big_M_solve_type(big_M_solve_type.clone()));
+                assert_eq!(t.additional_info, SolveMessage::Infeasible, "Failed with {:?} and {:?}.", variable_select_type, big_M_solve_type);
+            }
+        }
+    }
+    #[test]
+    fn solve_returns_solution(){
+        // max 3x + 5y s.t. x <= 4, 2y <= 12, 3x + 2y <= 18
+        let A = [vec![1f64,0f64,3f64], vec![0f64,2f64,2f64], vec![1f64,0f64,0f64], vec![0f64,1f64,0f64], vec![0f64,0f64,1f64]];
+        let A: Vec<&[f64]> = A.iter().map(|col| col.as_slice()).collect();
+        let mut t = Tableau::new(&A, &[4f64,12f64,18f64], &[3f64,5f64,0f64,0f64,0f64], &SolverOptions::new().variable_select_type(VariableSelectType::Standard)).unwrap();
+        let solution = t.solve().unwrap();
+        assert_eq!(solution.status, SolveStatus::Optimal);
+        assert_eq!(solution.objective, Fraction::from(36));
+        assert_eq!(solution.values, vec![Fraction::from(2), Fraction::from(6), Fraction::from(2), Fraction::from(0), Fraction::from(0)]);
+        assert_eq!(solution.basis, vec![2, 1, 0]);
+        assert_eq!(solution.iterations, 2);
+        // solving again gives back the same result
+        assert_eq!(t.solve().unwrap(), solution);
+    }
+    #[test]
+    fn solve_returns_status(){
+        let A = [vec![1f64], vec![-1f64], vec![1f64]];
+        let A: Vec<&[f64]> = A.iter().map(|col| col.as_slice()).collect();
+        let mut t = Tableau::new(&A, &[1f64], &[1f64,1f64,0f64], &SolverOptions::new().variable_select_type(VariableSelectType::Standard)).unwrap();
+        assert_eq!(t.solve().unwrap().status, SolveStatus::Unbounded);
+
+        let A = [vec![1f64,1f64], vec![1f64,1f64], vec![1f64,0f64], vec![0f64,1f64]];
+        let A: Vec<&[f64]> = A.iter().map(|col| col.as_slice()).collect();
+        let mut t = Tableau::new(&A, &[4f64,2f64], &[1f64,1f64,-f64::MAX,0f64], &SolverOptions::new().variable_select_type(VariableSelectType::Bland)).unwrap();
+        let solution = t.solve().unwrap();
+        assert_eq!(solution.status, SolveStatus::Infeasible);
+        assert!(solution.values.is_empty());
+    }
+    #[test]
+    fn new_reports_errors(){
+        let new = |A: &[&[f64]], b: &[f64], c: &[f64]| {
+            Tableau::new(A, b, c, &SolverOptions::new()).err()
+        };
+        let A: [&[f64]; 3] = [&[1f64,0f64], &[0f64,1f64], &[1f64,1f64]];
+        assert_eq!(new(&[], &[], &[]), Some(SimplexError::EmptyProblem));
+        assert_eq!(new(&[&[1f64,0f64], &[1f64]], &[1f64,1f64], &[1f64,1f64]), Some(SimplexError::RaggedMatrix { column: 1, rows: 1, expected_rows: 2 }));
+        assert_eq!(new(&A, &[1f64,1f64], &[1f64,1f64]), Some(SimplexError::CostDimensionMismatch { columns: 3, costs: 2 }));
+        assert_eq!(new(&A, &[1f64], &[1f64,1f64,1f64]), Some(SimplexError::RightHandSideDimensionMismatch { rows: 2, right_hand_sides: 1 }));
+        assert!(matches!(new(&A, &[1f64,1f64], &[1f64,1f64,f64::NAN]), Some(SimplexError::UnrepresentableValue(_))));
+
+        // max 2x + 3y s.t. x + y = 4, x + 3y + s = 6 with an artificial variable in the first row
+        let A: [&[f64]; 4] = [&[1f64,1f64], &[1f64,3f64], &[0f64,1f64], &[1f64,0f64]];
+        let options = SolverOptions::new().solve_type(SolveType::Dual).big_M_solve_type(BigMSolveType::Detached);
+        assert_eq!(Tableau::new(&A, &[4f64,6f64], &[2f64,3f64,0f64,-f64::MAX], &options).err(), Some(SimplexError::UnsupportedArtificialVariable { column: 3 }));
+        let two_phase = options.clone().big_M_solve_type(BigMSolveType::TwoPhase);
+        assert_eq!(Tableau::new(&A, &[4f64,6f64], &[2f64,3f64,0f64,f64::MAX], &two_phase).err(), Some(SimplexError::UnsupportedArtificialVariable { column: 3 }));
+        let mut t = Tableau::new(&A, &[4f64,6f64], &[2f64,3f64,0f64,-f64::MAX], &options.solve_type(SolveType::Standard)).unwrap();
+        assert_eq!(t.solve().unwrap().objective, Fraction::from(9));
+    }
+    #[test]
+    fn error_messages(){
+        assert_eq!(format!("{}", SimplexError::InvalidEnteringVariable { column: 2 }), "The pricing rule chose the 3rd variable, which cannot improve the objective.");
+        assert_eq!(format!("{}", SimplexError::InvalidLeavingRow { row: 0 }), "The ratio test rule chose the 1st row, which does not tie for the minimum ratio.");
+        assert_eq!(format!("{}", SimplexError::UnsupportedArtificialVariable { column: 3 }), "The 4th variable is artificial, which is not supported by this solve type.");
+    }
+    #[test]
+    fn iteration_limit(){
+        // max 3x + 5y s.t. x <= 4, 2y <= 12, 3x + 2y <= 18 takes 2 pivots with the standard rule
+        let A = [vec![1f64,0f64,3f64], vec![0f64,2f64,2f64], vec![1f64,0f64,0f64], vec![0f64,1f64,0f64], vec![0f64,0f64,1f64]];
+        let A: Vec<&[f64]> = A.iter().map(|col| col.as_slice()).collect();
+        let options = SolverOptions::new().variable_select_type(VariableSelectType::Standard).max_iterations(1);
+        let mut t = Tableau::new(&A, &[4f64,12f64,18f64], &[3f64,5f64,0f64,0f64,0f64], &options).unwrap();
+        // the solution is the basis we stopped at, after y entered the basis
+        let solution = t.solve().unwrap();
+        assert_eq!(solution.status, SolveStatus::IterationLimit);
+        assert_eq!(solution.iterations, 1);
+        assert_eq!(solution.objective, Fraction::from(30));
+        assert_eq!(solution.values, vec![Fraction::from(0), Fraction::from(6), Fraction::from(4), Fraction::from(0), Fraction::from(6)]);
+        assert_eq!(solution.duals, None);
+
+        let mut t = Tableau::new(&A, &[4f64,12f64,18f64], &[3f64,5f64,0f64,0f64,0f64], &options.max_iterations(2)).unwrap();
+        assert_eq!(t.solve().unwrap().objective, Fraction::from(36));
+    }
+    #[test]
+    fn minimize(){
+        // min x + y s.t. x + 2y >= 4, 3x + y >= 3, where either sign of f64::MAX marks the artificial variables
+        let A = vec![vec![1f64,3f64], vec![2f64,1f64], vec![-1f64,0f64], vec![1f64,0f64], vec![0f64,-1f64], vec![0f64,1f64]];
+        let b = vec![4f64,3f64];
+        let options = SolverOptions::new().objective_sense(ObjectiveSense::Minimize);
+        for big_M in [f64::MAX, -f64::MAX].iter() {
+            let c = vec![1f64,1f64,0f64,*big_M,0f64,*big_M];
+            let columns: Vec<&[f64]> = A.iter().map(|col| col.as_slice()).collect();
+            let t = Tableau::new(&columns, &b, &c, &options).unwrap();
+            assert_eq!(t.c[3], -BigM::M());
+            for big_M_solve_type in [BigMSolveType::TwoPhase, BigMSolveType::Detached].iter() {
+                let t = solve(&A, &b, &c, options.clone().big_M_solve_type(big_M_solve_type.clone()));
+                assert_eq!(t.additional_info, SolveMessage::Optimal);
+                assert_eq!(t.objective_value(), Fraction::new(11,5));
+                assert_eq!(t.solution, vec![Fraction::new(2,5), Fraction::new(9,5), Fraction::from(0), Fraction::from(0)]);
+            }
+
+            // max -x - y over the same region gives the same point
+            let c = vec![-1f64,-1f64,0f64,*big_M,0f64,*big_M];
+            for big_M_solve_type in [BigMSolveType::TwoPhase, BigMSolveType::Detached].iter() {
+                let t = solve(&A, &b, &c, SolverOptions::new().big_M_solve_type(big_M_solve_type.clone()));
+                assert_eq!(t.additional_info, SolveMessage::Optimal);
+                assert_eq!(t.objective_value(), Fraction::new(-11,5));
+                assert_eq!(t.solution, vec![Fraction::new(2,5), Fraction::new(9,5), Fraction::from(0), Fraction::from(0)]);
+            }
+        }
+
+        // min 2x + 3y s.t. x + y >= 2, written as -x - y + s = -2 for the dual simplex method
+        let A = [vec![-1f64], vec![-1f64], vec![1f64]];
+        let A: Vec<&[f64]> = A.iter().map(|col| col.as_slice()).collect();
+        let mut t = Tableau::new(&A, &[-2f64], &[2f64,3f64,0f64], &options.solve_type(SolveType::Dual)).unwrap();
+        let solution = t.solve().unwrap();
+        assert_eq!(solution.status, SolveStatus::Optimal);
+        assert_eq!(solution.objective, Fraction::from(4));
+        assert_eq!(solution.values, vec![Fraction::from(2), Fraction::from(0), Fraction::from(0)]);
+    }
+    #[test]
+    fn bounded_variables(){
+        // max 3x + 5y s.t. 3x + 2y <= 18, 0 <= x <= 4, 0 <= y <= 6, with the bounds kept out of A
+        let A = [vec![3f64], vec![2f64], vec![1f64]];
+        let A: Vec<&[f64]> = A.iter().map(|col| col.as_slice()).collect();
+        let lower = [0f64,0f64,0f64];
+        let upper = [4f64,6f64,f64::INFINITY];
+        for options in [SolverOptions::new(), SolverOptions::new().variable_select_type(VariableSelectType::Standard), SolverOptions::new().solve_type(SolveType::Revised)].iter() {
+            let mut t = Tableau::with_bounds(&A, &[18f64], &[3f64,5f64,0f64], &lower, &upper, options).unwrap();
+            let solution = t.solve().unwrap();
+            assert_eq!(t.m, 1);
+            assert_eq!(solution.status, SolveStatus::Optimal);
+            assert_eq!(solution.objective, Fraction::from(36));
+            assert_eq!(solution.values, vec![Fraction::from(2), Fraction::from(6), Fraction::from(0)]);
+        }
+
+        // max x s.t. x <= 10 and x <= 4 only needs a bound flip
+        let A = [vec![1f64], vec![1f64]];
+        let A: Vec<&[f64]> = A.iter().map(|col| col.as_slice()).collect();
+        let mut t = Tableau::with_bounds(&A, &[10f64], &[1f64,0f64], &[0f64,0f64], &[4f64,f64::INFINITY], &SolverOptions::new()).unwrap();
+        let solution = t.solve().unwrap();
+        assert_eq!(solution.values, vec![Fraction::from(4), Fraction::from(6)]);
+        assert_eq!(solution.basis, vec![1]);
+        assert_eq!(solution.iterations, 1);
+
+        // max -x s.t. x - y = 2 with x fixed at -1 and y >= 0 has no feasible solution, because x cannot start in the basis at 3 past its lower bound
+        let A = [vec![1f64], vec![-1f64]];
+        let A: Vec<&[f64]> = A.iter().map(|col| col.as_slice()).collect();
+        for options in [SolverOptions::new(), SolverOptions::new().big_M_solve_type(BigMSolveType::Detached), SolverOptions::new().solve_type(SolveType::Revised)].iter() {
+            let mut t = Tableau::with_bounds(&A, &[2f64], &[-1f64,0f64], &[-1f64,0f64], &[-1f64,f64::INFINITY], options).unwrap();
+            assert_eq!(t.solve().unwrap().status, SolveStatus::Infeasible);
+        }
+    }
+    #[test]
+    fn free_and_shifted_variables(){
+        // max -x s.t. -x <= 5 with x free
+        let A = [vec![-1f64], vec![1f64]];
+        let A: Vec<&[f64]> = A.iter().map(|col| col.as_slice()).collect();
+        let mut t = Tableau::with_bounds(&A, &[5f64], &[-1f64,0f64], &[f64::NEG_INFINITY,0f64], &[f64::INFINITY,f64::INFINITY], &SolverOptions::new()).unwrap();
+        let solution = t.solve().unwrap();
+        assert_eq!(solution.objective, Fraction::from(5));
+        assert_eq!(solution.values, vec![Fraction::from(-5), Fraction::from(0)]);
+
+        // max 2x + y s.t. x + y <= 10, x <= 3 and y >= 1, where x has no lower bound
+        let A = [vec![1f64], vec![1f64], vec![1f64]];
+        let A: Vec<&[f64]> = A.iter().map(|col| col.as_slice()).collect();
+        for options in [SolverOptions::new(), SolverOptions::new().solve_type(SolveType::Revised)].iter() {
+            let mut t = Tableau::with_bounds(&A, &[10f64], &[2f64,1f64,0f64], &[f64::NEG_INFINITY,1f64,0f64], &[3f64,f64::INFINITY,f64::INFINITY], options).unwrap();
+            let solution = t.solve().unwrap();
+            assert_eq!(solution.objective, Fraction::from(13));
+            assert_eq!(solution.values, vec![Fraction::from(3), Fraction::from(7), Fraction::from(0)]);
+        }
+    }
+    #[test]
+    fn invalid_bounds(){
+        let A = [vec![1f64], vec![1f64]];
+        let A: Vec<&[f64]> = A.iter().map(|col| col.as_slice()).collect();
+        let with_bounds = |lower: &[f64], upper: &[f64], options: SolverOptions| {
+            Tableau::with_bounds(&A, &[1f64], &[1f64,0f64], lower, upper, &options).err()
+        };
+        assert_eq!(with_bounds(&[0f64], &[1f64,1f64], SolverOptions::new()), Some(SimplexError::BoundsDimensionMismatch { columns: 2, bounds: 1 }));
+        assert_eq!(with_bounds(&[0f64,2f64], &[1f64,1f64], SolverOptions::new()), Some(SimplexError::InvalidBounds { column: 1 }));
+        assert_eq!(with_bounds(&[f64::INFINITY,0f64], &[f64::INFINITY,1f64], SolverOptions::new()), Some(SimplexError::InvalidBounds { column: 0 }));
+        assert_eq!(with_bounds(&[0f64,0f64], &[1f64,1f64], SolverOptions::new().solve_type(SolveType::Dual)), Some(SimplexError::UnsupportedBounds { column: 0 }));
+    }
+    #[test]
+    fn duals_and_reduced_costs(){
+        // max 3x + 5y s.t. x <= 4, 2y <= 12, 3x + 2y <= 18
+        let A = [vec![1f64,0f64,3f64], vec![0f64,2f64,2f64], vec![1f64,0f64,0f64], vec![0f64,1f64,0f64], vec![0f64,0f64,1f64]];
+        for options in [SolverOptions::new(), SolverOptions::new().solve_type(SolveType::Revised)].iter() {
+            let A: Vec<&[f64]> = A.iter().map(|col| col.as_slice()).collect();
+            let mut t = Tableau::new(&A, &[4f64,12f64,18f64], &[3f64,5f64,0f64,0f64,0f64], options).unwrap();
+            let solution = t.solve().unwrap();
+            assert_eq!(solution.duals, Some(vec![Fraction::from(0), Fraction::new(3,2), Fraction::from(1)]));
+            assert_eq!(solution.reduced_costs, Some(vec![Fraction::from(0), Fraction::from(0), Fraction::from(0), Fraction::new(-3,2), Fraction::from(-1)]));
+        }
+
+        // min x + y s.t. x + 2y >= 4, 3x + y >= 3, where the duals come from the artificial variables that were removed after Phase 1
+        let A = vec![vec![1f64,3f64], vec![2f64,1f64], vec![-1f64,0f64], vec![1f64,0f64], vec![0f64,-1f64], vec![0f64,1f64]];
+        let options = SolverOptions::new().objective_sense(ObjectiveSense::Minimize);
+        for options in [options.clone(), options.clone().solve_type(SolveType::Revised), options.clone().big_M_solve_type(BigMSolveType::Detached)].iter() {
+            let t = solve(&A, &[4f64,3f64], &[1f64,1f64,0f64,f64::MAX,0f64,f64::MAX], options.clone());
+            assert_eq!(t.dual_values(), Some(vec![Fraction::new(2,5), Fraction::new(1,5)]));
+            assert_eq!(t.reduced_costs(), Some(vec![Fraction::from(0), Fraction::from(0), Fraction::new(2,5), Fraction::new(1,5)]));
+        }
+
+        // min 2x + 3y s.t. x + y >= 2, written as -x - y + s = -2 for the dual simplex method
+        let A = [vec![-1f64], vec![-1f64], vec![1f64]];
+        let t = solve(&A, &[-2f64], &[2f64,3f64,0f64], options.solve_type(SolveType::Dual));
+        assert_eq!(t.dual_values(), Some(vec![Fraction::from(-2)]));
+        assert_eq!(t.reduced_costs(), Some(vec![Fraction::from(0), Fraction::from(1), Fraction::from(2)]));
+
+        // min x + 3y s.t. x + 2y = 4, x + s = 3, where the dual simplex method has to pivot x into the first row before it starts
+        let A = [vec![1f64,1f64], vec![2f64,0f64], vec![0f64,1f64]];
+        let options = SolverOptions::new().objective_sense(ObjectiveSense::Minimize);
+        for options in [options.clone(), options.clone().solve_type(SolveType::Dual), options.clone().solve_type(SolveType::Dual).big_M_solve_type(BigMSolveType::Detached)].iter() {
+            let t = solve(&A, &[4f64,3f64], &[1f64,3f64,0f64], options.clone());
+            assert_eq!(t.objective_value(), Fraction::new(9,2));
+            assert_eq!(t.dual_values(), Some(vec![Fraction::new(3,2), Fraction::new(-1,2)]));
+        }
+
+        // the same with the first row repeated, which the dual simplex method drops as redundant, so the duals still have to give the objective value and reduced costs
+        let A = [vec![1f64,2f64,1f64], vec![2f64,4f64,0f64], vec![0f64,0f64,1f64]];
+        let t = solve(&A, &[4f64,8f64,3f64], &[1f64,3f64,0f64], options.solve_type(SolveType::Dual));
+        assert_eq!(t.m, 2);
+        let duals = t.dual_values().unwrap();
+        let dot = |column: &[f64]| (0..3).fold(Fraction::from(0), |sum, row| sum + duals[row].clone() * Fraction::from(column[row]));
+        assert_eq!(dot(&[4f64,8f64,3f64]), Fraction::new(9,2));
+        let reduced_costs = t.reduced_costs().unwrap();
+        for (col, cost) in [1f64,3f64,0f64].iter().enumerate() {
+            assert_eq!(reduced_costs[col], Fraction::from(*cost) - dot(&A[col]));
+        }
+    }
+    #[test]
+    fn sensitivity(){
+        // max 3x + 5y s.t. x <= 4, 2y <= 12, 3x + 2y <= 18
+        let A = [vec![1f64,0f64,3f64], vec![0f64,2f64,2f64], vec![1f64,0f64,0f64], vec![0f64,1f64,0f64], vec![0f64,0f64,1f64]];
+        let range = |value: i64, lower: Option<Fraction>, upper: Option<Fraction>| SensitivityRange { value: Fraction::from(value), lower, upper };
+        for options in [SolverOptions::new(), SolverOptions::new().solve_type(SolveType::Revised)].iter() {
+            let t = solve(&A, &[4f64,12f64,18f64], &[3f64,5f64,0f64,0f64,0f64], options.clone());
+            let report = t.sensitivity().unwrap();
+            assert_eq!(report.objective, vec![
+                range(3, Some(Fraction::from(0)), Some(Fraction::new(15,2))),
+                range(5, Some(Fraction::from(2)), None),
+                range(0, Some(Fraction::new(-9,2)), Some(Fraction::from(3))),
+                range(0, None, Some(Fraction::new(3,2))),
+                range(0, None, Some(Fraction::from(1))),
+            ]);
+            assert_eq!(report.rhs, vec![
+                range(4, Some(Fraction::from(2)), None),
+                range(12, Some(Fraction::from(6)), Some(Fraction::from(18))),
+                range(18, Some(Fraction::from(12)), Some(Fraction::from(24))),
+            ]);
+        }
+
+        // the same ranges for min -3x - 5y
+        let t = solve(&A, &[4f64,12f64,18f64], &[-3f64,-5f64,0f64,0f64,0f64], SolverOptions::new().objective_sense(ObjectiveSense::Minimize));
+        let report = t.sensitivity().unwrap();
+        assert_eq!(report.objective[0], range(-3, Some(Fraction::new(-15,2)), Some(Fraction::from(0))));
+        assert_eq!(report.objective[1], range(-5, None, Some(Fraction::from(-2))));
+        assert_eq!(report.rhs[2], range(18, Some(Fraction::from(12)), Some(Fraction::from(24))));
+    }
+    #[test]
+    fn dual_phase_1(){
+        // max x + 2y s.t. x <= 800, y <= 900, x + y >= 1500, where the slack basis is neither primal nor dual feasible
+        let A = [vec![1f64,0f64,-1f64], vec![0f64,1f64,-1f64], vec![1f64,0f64,0f64], vec![0f64,1f64,0f64], vec![0f64,0f64,1f64]];
+        let t = solve(&A, &[800f64,900f64,-1500f64], &[1f64,2f64,0f64,0f64,0f64], SolverOptions::new().solve_type(SolveType::Dual));
+        assert_eq!(t.additional_info, SolveMessage::Optimal);
+        assert_eq!(t.objective_value(), Fraction::from(2600));
+        assert_eq!(t.solution, vec![Fraction::from(800), Fraction::from(900), Fraction::from(0), Fraction::from(0), Fraction::from(200)]);
+        assert_eq!(t.dual_values(), Some(vec![Fraction::from(1), Fraction::from(2), Fraction::from(0)]));
+
+        // max x s.t. -x <= 5 is unbounded
+        let A = [vec![-1f64], vec![1f64]];
+        let t = solve(&A, &[5f64], &[1f64,0f64], SolverOptions::new().solve_type(SolveType::Dual));
+        assert_eq!(t.additional_info, SolveMessage::Unbounded);
+
+        // max x s.t. x <= 1, x >= 3 is infeasible
+        let A = [vec![1f64,-1f64], vec![1f64,0f64], vec![0f64,1f64]];
+        let t = solve(&A, &[1f64,-3f64], &[1f64,0f64,0f64], SolverOptions::new().solve_type(SolveType::Dual));
+        assert_eq!(t.additional_info, SolveMessage::Infeasible);
+    }
+    #[test]
+    fn automatic_starting_basis(){
+        // min x + y s.t. x + 2y = 4, 3x + y = 3, with no columns of I in A
+        let A = [vec![1f64,3f64], vec![2f64,1f64]];
+        let options = SolverOptions::new().objective_sense(ObjectiveSense::Minimize);
+        for options in [options.clone(), options.clone().big_M_solve_type(BigMSolveType::Detached), options.clone().solve_type(SolveType::Revised)].iter() {
+            let t = solve(&A, &[4f64,3f64], &[1f64,1f64], options.clone());
+            assert_eq!(t.additional_info, SolveMessage::Optimal);
+            assert_eq!(t.objective_value(), Fraction::new(11,5));
+            assert_eq!(t.solution, vec![Fraction::new(2,5), Fraction::new(9,5)]);
+            assert_eq!(t.dual_values(), Some(vec![Fraction::new(2,5), Fraction::new(1,5)]));
+        }
+
+        // max x + y s.t. x + y <= 4 with a slack column, and x - y = 1 without one
+        let A = [vec![1f64,1f64], vec![1f64,-1f64], vec![1f64,0f64]];
+        let t = solve(&A, &[4f64,1f64], &[1f64,1f64,0f64], SolverOptions::new());
+        assert_eq!(t.objective_value(), Fraction::from(4));
+        assert_eq!(t.solution, vec![Fraction::new(5,2), Fraction::new(3,2), Fraction::from(0)]);
+
+        // max x s.t. x + 2y = 3 with -1 <= x <= -0.5, where the column of x is a column of I but x cannot start in the basis at 3
+        let A = [vec![1f64], vec![2f64]];
+        let A: Vec<&[f64]> = A.iter().map(|col| col.as_slice()).collect();
+        for options in [SolverOptions::new(), SolverOptions::new().big_M_solve_type(BigMSolveType::Detached)].iter() {
+            let mut t = Tableau::with_bounds(&A, &[3f64], &[1f64,0f64], &[-1f64,0f64], &[-0.5f64,f64::INFINITY], options).unwrap();
+            let solution = t.solve().unwrap();
+            assert_eq!(solution.status, SolveStatus::Optimal);
+            assert_eq!(solution.values, vec![Fraction::new(-1,2), Fraction::new(7,4)]);
+        }
+
+        // x + y = 1, x + y = 2 has no feasible solution
+        let A = [vec![1f64,1f64], vec![1f64,1f64]];
+        let t = solve(&A, &[1f64,2f64], &[1f64,1f64], SolverOptions::new());
+        assert_eq!(t.additional_info, SolveMessage::Infeasible);
+    }
+    #[test]
+    fn negative_right_hand_side(){
+        // min x + y s.t. x + 2y >= 4, 3x + y >= 3, written as -x - 2y + s1 = -4 and -3x - y + s2 = -3
+        let A = [vec![-1f64,-3f64], vec![-2f64,-1f64], vec![1f64,0f64], vec![0f64,1f64]];
+        let options = SolverOptions::new().objective_sense(ObjectiveSense::Minimize);
+        for options in [options.clone(), options.clone().big_M_solve_type(BigMSolveType::Detached), options.clone().solve_type(SolveType::Revised), options.clone().solve_type(SolveType::Dual)].iter() {
+            let t = solve(&A, &[-4f64,-3f64], &[1f64,1f64,0f64,0f64], options.clone());
+            assert_eq!(t.additional_info, SolveMessage::Optimal);
+            assert_eq!(t.objective_value(), Fraction::new(11,5));
+            assert_eq!(t.solution, vec![Fraction::new(2,5), Fraction::new(9,5), Fraction::from(0), Fraction::from(0)]);
+            // raising the right hand side of a >= constraint written this way loosens it, which lowers the minimum
+            assert_eq!(t.dual_values(), Some(vec![Fraction::new(-2,5), Fraction::new(-1,5)]));
+        }
+
+        // max x s.t. x - s = 1 and 2 <= x <= 3, where shifting x by its lower bound makes b negative
+        let A = [vec![1f64], vec![-1f64]];
+        let A: Vec<&[f64]> = A.iter().map(|col| col.as_slice()).collect();
+        let mut t = Tableau::with_bounds(&A, &[1f64], &[1f64,0f64], &[2f64,0f64], &[3f64,f64::INFINITY], &SolverOptions::new()).unwrap();
+        let solution = t.solve().unwrap();
+        assert_eq!(solution.status, SolveStatus::Optimal);
+        assert_eq!(solution.values, vec![Fraction::from(3), Fraction::from(2)]);
+        assert_eq!(solution.duals, Some(vec![Fraction::from(0)]));
+    }
+    #[test]
+    fn steepest_edge(){
+        // max 3x + 5y s.t. x <= 4, 2y <= 12, 3x + 2y <= 18
+        let A = [vec![1f64,0f64,3f64], vec![0f64,2f64,2f64], vec![1f64,0f64,0f64], vec![0f64,1f64,0f64], vec![0f64,0f64,1f64]];
+        for solve_type in [SolveType::Standard, SolveType::Revised].iter() {
+            let options = SolverOptions::new().variable_select_type(VariableSelectType::SteepestEdge).solve_type(solve_type.clone());
+            let t = solve(&A, &[4f64,12f64,18f64], &[3f64,5f64,0f64,0f64,0f64], options);
+            assert_eq!(t.objective_value(), Fraction::from(36));
+
+            // the updated weights should match the weights computed from scratch for every nonbasic column
+            let mut exact = t.clone();
+            exact.compute_reference_weights();
+            for col in 0..t.n {
+                if !t.basis_indecies.contains(&col) {
+                    assert_eq!(t.reference_weights[col], exact.reference_weights[col]);
                 }
-                self.entering_variable_index = col;
-                most_negative_value = Fraction::from(self.reduced_cost[col].clone());
             }
         }
 
-        self.original_basis_indecies = None;
-        self.leaving_variable_index = self.m-1;
-        self.basis_indecies.push(self.m-1);
-        self.update();
+        // Beale's example
+        let A = [vec![0.25f64,0.5f64,0f64], vec![-8f64,-0.5f64,0f64], vec![-1f64,-0.5f64,1f64], vec![9f64,3f64,0f64], vec![1f64,0f64,0f64], vec![0f64,1f64,0f64], vec![0f64,0f64,1f64]];
+        let t = solve(&A, &[0f64,0f64,1f64], &[0.75f64,-20f64,0.5f64,-6f64,0f64,0f64,0f64], SolverOptions::new().variable_select_type(VariableSelectType::SteepestEdge));
+        assert_eq!(t.objective_value(), Fraction::new(5,4));
+
+        // min x + y s.t. x + 2y = 4, 3x + y = 3, which runs Phase 1 first
+        let A = [vec![1f64,3f64], vec![2f64,1f64]];
+        let options = SolverOptions::new().objective_sense(ObjectiveSense::Minimize).variable_select_type(VariableSelectType::SteepestEdge);
+        let t = solve(&A, &[4f64,3f64], &[1f64,1f64], options);
+        assert_eq!(t.objective_value(), Fraction::new(11,5));
+    }
+    #[test]
+    fn devex_and_statistics(){
+        // Beale's example
+        let A = [vec![0.25f64,0.5f64,0f64], vec![-8f64,-0.5f64,0f64], vec![-1f64,-0.5f64,1f64], vec![9f64,3f64,0f64], vec![1f64,0f64,0f64], vec![0f64,1f64,0f64], vec![0f64,0f64,1f64]];
+        let A: Vec<&[f64]> = A.iter().map(|col| col.as_slice()).collect();
+        let mut weights = Vec::new();
+        for solve_type in [SolveType::Standard, SolveType::Revised].iter() {
+            let options = SolverOptions::new().variable_select_type(VariableSelectType::Devex).solve_type(solve_type.clone());
+            let mut t = Tableau::new(&A, &[0f64,0f64,1f64], &[0.75f64,-20f64,0.5f64,-6f64,0f64,0f64,0f64], &options).unwrap();
+            let solution = t.solve().unwrap();
+            weights.push(t.reference_weights.clone());
+            assert_eq!(solution.objective, Fraction::new(5,4));
+            assert_eq!(solution.statistics.variable_select_type, VariableSelectType::Devex);
+            assert_eq!(solution.statistics.iterations, solution.iterations);
+            assert_eq!(solution.statistics.reference_resets, 1);
+            assert!(solution.statistics.degenerate_pivots > 0);
+
+            // the framework can be reset every iteration, or never after the first one
+            let mut t = Tableau::new(&A, &[0f64,0f64,1f64], &[0.75f64,-20f64,0.5f64,-6f64,0f64,0f64,0f64], &options.clone().devex_reset_iterations(1)).unwrap();
+            let solution = t.solve().unwrap();
+            assert_eq!(solution.objective, Fraction::new(5,4));
+            assert_eq!(solution.statistics.reference_resets, solution.iterations + 1);
+            let mut t = Tableau::new(&A, &[0f64,0f64,1f64], &[0.75f64,-20f64,0.5f64,-6f64,0f64,0f64,0f64], &options.clone().devex_reset_iterations(0)).unwrap();
+            assert_eq!(t.solve().unwrap().statistics.reference_resets, 1);
+        }
+        // the revised method finds the leaving row of B^-1 * A from B^-1, which gives the same weights as the full tableau
+        assert_eq!(weights[0], weights[1]);
+
+        // max x s.t. x <= 10 and x <= 4 only needs a bound flip
+        let A = [vec![1f64], vec![1f64]];
+        let A: Vec<&[f64]> = A.iter().map(|col| col.as_slice()).collect();
+        let mut t = Tableau::with_bounds(&A, &[10f64], &[1f64,0f64], &[0f64,0f64], &[4f64,f64::INFINITY], &SolverOptions::new()).unwrap();
+        let statistics = t.solve().unwrap().statistics;
+        assert_eq!(statistics.bound_flips, 1);
+        assert_eq!(statistics.degenerate_pivots, 0);
+        assert_eq!(statistics.phase_1_iterations, 0);
+
+        // min x + y s.t. x + 2y = 4, 3x + y = 3 needs two pivots to remove the artificial variables
+        let A = [vec![1f64,3f64], vec![2f64,1f64]];
+        let A: Vec<&[f64]> = A.iter().map(|col| col.as_slice()).collect();
+        let options = SolverOptions::new().objective_sense(ObjectiveSense::Minimize).variable_select_type(VariableSelectType::Devex);
+        let mut t = Tableau::new(&A, &[4f64,3f64], &[1f64,1f64], &options).unwrap();
+        let solution = t.solve().unwrap();
+        assert_eq!(solution.objective, Fraction::new(11,5));
+        assert_eq!(solution.statistics.phase_1_iterations, 2);
     }
+    #[test]
+    fn custom_pricing_rule(){
+        use std::sync::{Arc, Mutex};
+        use super::super::pricing::{PricingRule, PricingContext};
+
+        // steepest edge computed from scratch each iteration, which also records the iterations it was asked about
+        struct ExactSteepestEdge {
+            iterations: Arc<Mutex<Vec<usize>>>,
+        }
+        impl PricingRule for ExactSteepestEdge {
+            fn choose(&self, context: &PricingContext) -> Option<usize> {
+                self.iterations.lock().unwrap().push(context.iteration());
+                let mut entering = None;
+                let mut best = Fraction::from(0);
+                for col in 0..context.number_of_variables() {
+                    if context.direction(col) == 0 {
+                        continue;
+                    }
+                    let mut weight = Fraction::from(1);
+                    for entry in context.column(col) {
+                        weight = weight + entry.clone() * entry;
+                    }
+                    let improvement = context.improvement(col).constant;
+                    let score = improvement.clone() * improvement / weight;
+                    if score > best {
+                        best = score;
+                        entering = Some(col);
+                    }
+                }
+                entering
+            }
+        }
+
+        // max 3x + 5y s.t. x <= 4, 2y <= 12, 3x + 2y <= 18
+        let A = [vec![1f64,0f64,3f64], vec![0f64,2f64,2f64], vec![1f64,0f64,0f64], vec![0f64,1f64,0f64], vec![0f64,0f64,1f64]];
+        for solve_type in [SolveType::Standard, SolveType::Revised].iter() {
+            let iterations = Arc::new(Mutex::new(Vec::new()));
+            let options = SolverOptions::new().pricing_rule(ExactSteepestEdge { iterations: iterations.clone() }).solve_type(solve_type.clone());
+            let steepest_edge = solve(&A, &[4f64,12f64,18f64], &[3f64,5f64,0f64,0f64,0f64], options);
+            let expected = solve(&A, &[4f64,12f64,18f64], &[3f64,5f64,0f64,0f64,0f64], SolverOptions::new().variable_select_type(VariableSelectType::SteepestEdge).solve_type(solve_type.clone()));
+            assert_eq!(steepest_edge.objective_value(), Fraction::from(36));
+            assert_eq!(steepest_edge.basis_indecies, expected.basis_indecies);
+            assert_eq!(steepest_edge.iterations, expected.iterations);
+            // the rule is asked once per pivot, and once more to find that the tableau is optimal
+            assert_eq!(*iterations.lock().unwrap(), (0..=steepest_edge.iterations).collect::<Vec<usize>>());
+        }
 
+        // custom rules can be sent to other threads along with the options that hold them
+        fn assert_send_sync<S: Send + Sync>(_: &S) {}
+        assert_send_sync(&SolverOptions::new().pricing_rule(ExactSteepestEdge { iterations: Arc::new(Mutex::new(Vec::new())) }).variable_select_type);
+
+        // the built in rules go through the same trait, and cloned options share the same custom rule
+        let options = SolverOptions::new().pricing_rule::<Fraction, _>(super::super::pricing::Bland);
+        assert_eq!(options.clone(), options);
+        assert_ne!(SolverOptions::new().pricing_rule::<Fraction, _>(super::super::pricing::Bland), options);
+        let custom = solve(&A, &[4f64,12f64,18f64], &[3f64,5f64,0f64,0f64,0f64], options);
+        let bland = solve(&A, &[4f64,12f64,18f64], &[3f64,5f64,0f64,0f64,0f64], SolverOptions::new());
+        assert_eq!(custom.basis_indecies, bland.basis_indecies);
+        assert_eq!(custom.iterations, bland.iterations);
+
+        // a rule that chooses a variable which cannot improve the objective is reported as an error
+        struct Fixed(usize);
+        impl PricingRule for Fixed {
+            fn choose(&self, _context: &PricingContext) -> Option<usize> {
+                Some(self.0)
+            }
+        }
+        let A: Vec<&[f64]> = A.iter().map(|col| col.as_slice()).collect();
+        let mut t = Tableau::new(&A, &[4f64,12f64,18f64], &[3f64,5f64,0f64,0f64,0f64], &SolverOptions::new().pricing_rule(Fixed(2))).unwrap();
+        assert_eq!(t.solve(), Err(SimplexError::InvalidEnteringVariable { column: 2 }));
+        let mut t = Tableau::new(&A, &[4f64,12f64,18f64], &[3f64,5f64,0f64,0f64,0f64], &SolverOptions::new().pricing_rule(Fixed(10))).unwrap();
+        assert_eq!(t.solve(), Err(SimplexError::InvalidEnteringVariable { column: 10 }));
+    }
+    #[test]
+    fn ratio_test_rules(){
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use super::super::ratio_test::{RatioTestRule, RatioTestContext};
+
+        // max 10x1 - 57x2 - 9x3 - 24x4 s.t. 0.5x1 - 5.5x2 - 2.5x3 + 9x4 <= 0, 0.5x1 - 1.5x2 - 0.5x3 + x4 <= 0, x1 <= 1,
+        // which cycles with the most negative reduced cost whether ties go to the lowest row or the lowest basic variable
+        let A = [vec![0.5f64,0.5f64,1f64], vec![-5.5f64,-1.5f64,0f64], vec![-2.5f64,-0.5f64,0f64], vec![9f64,1f64,0f64], vec![1f64,0f64,0f64], vec![0f64,1f64,0f64], vec![0f64,0f64,1f64]];
+        let A: Vec<&[f64]> = A.iter().map(|col| col.as_slice()).collect();
+        let b = [0f64,0f64,1f64];
+        let c = [10f64,-57f64,-9f64,-24f64,0f64,0f64,0f64];
+
+        let options = SolverOptions::new().variable_select_type(VariableSelectType::Standard).detect_cycles(true).max_iterations(50);
+        let mut t = Tableau::new(&A, &b, &c, &options).unwrap();
+        assert_eq!(t.solve().unwrap().status, SolveStatus::CycleDetected);
+        let mut t = Tableau::new(&A, &b, &c, &options.clone().ratio_test_type(RatioTestType::LowestBasicIndex)).unwrap();
+        assert_eq!(t.solve().unwrap().status, SolveStatus::CycleDetected);
+
+        for solve_type in [SolveType::Standard, SolveType::Revised].iter() {
+            let options = SolverOptions::new().variable_select_type(VariableSelectType::Standard).ratio_test_type(RatioTestType::Lexicographic).solve_type(solve_type.clone()).max_iterations(50);
+            let mut t = Tableau::new(&A, &b, &c, &options).unwrap();
+            assert_eq!(t.solve().unwrap().objective, Fraction::from(1));
+
+            let options = SolverOptions::new().variable_select_type(VariableSelectType::Bland).ratio_test_type(RatioTestType::LowestBasicIndex).solve_type(solve_type.clone()).max_iterations(50);
+            let mut t = Tableau::new(&A, &b, &c, &options).unwrap();
+            assert_eq!(t.solve().unwrap().objective, Fraction::from(1));
+        }
+
+        // takes the highest row, and counts how many ties it was asked to break
+        struct HighestRow {
+            ties: Arc<AtomicUsize>,
+        }
+        impl RatioTestRule for HighestRow {
+            fn choose(&self, context: &RatioTestContext) -> usize {
+                assert!(context.candidates().len() > 1);
+                assert_eq!(*context.ratio(), Fraction::from(0));
+                self.ties.fetch_add(1, Ordering::SeqCst);
+                context.candidates()[context.candidates().len()-1].row
+            }
+        }
+        let ties = Arc::new(AtomicUsize::new(0));
+        let options = SolverOptions::new().ratio_test_rule(HighestRow { ties: ties.clone() }).max_iterations(50);
+        let mut t = Tableau::new(&A, &b, &c, &options).unwrap();
+        assert_eq!(t.solve().unwrap().objective, Fraction::from(1));
+        assert!(ties.load(Ordering::SeqCst) > 0);
+        assert_eq!(t.basis_indecies, Tableau::new(&A, &b, &c, &SolverOptions::new()).unwrap().solve().unwrap().basis);
+
+        // options and tableaus with custom rules can be sent to other threads
+        fn assert_send_sync<S: Send + Sync>(_: &S) {}
+        assert_send_sync(&options);
+        assert_send_sync(&t);
+
+        // a rule that chooses a row which does not tie is reported as an error
+        struct OutOfRange;
+        impl RatioTestRule for OutOfRange {
+            fn choose(&self, _context: &RatioTestContext) -> usize {
+                99
+            }
+        }
+        let mut t = Tableau::new(&A, &b, &c, &SolverOptions::new().ratio_test_rule(OutOfRange)).unwrap();
+        assert_eq!(t.solve(), Err(SimplexError::InvalidLeavingRow { row: 99 }));
+
+        // max x s.t. x <= 0.7, 3x <= 2.1, where the ratio of the second row is larger only by rounding error.
+        // Read in exactly the first row has the minimum ratio, but in f64 the rows tie and the larger pivot leaves
+        let A = [vec![1f64,3f64], vec![1f64,0f64], vec![0f64,1f64]];
+        let A: Vec<&[f64]> = A.iter().map(|col| col.as_slice()).collect();
+        let options = SolverOptions::new().ratio_test_type(RatioTestType::LargestPivot);
+        let exact = Tableau::new(&A, &[0.7f64,2.1f64], &[1f64,0f64,0f64], &options).unwrap().solve().unwrap();
+        let rounded = Tableau::<f64>::with_scalar(&A, &[0.7f64,2.1f64], &[1f64,0f64,0f64], &options).unwrap().solve().unwrap();
+        assert_eq!(exact.basis, vec![0, 2]);
+        assert_eq!(rounded.basis, vec![1, 0]);
+    }
+    #[test]
+    fn lexicographic_ratio_test(){
+        let inf = f64::INFINITY;
+        let lexicographic = SolverOptions::new().variable_select_type(VariableSelectType::Standard).ratio_test_type(RatioTestType::Lexicographic).max_iterations(50);
+
+        // min -10x1 + 57x2 + 9x3 + 24x4 s.t. 0.5x1 - 5.5x2 - 2.5x3 + 9x4 <= 0, 0.5x1 - 1.5x2 - 0.5x3 + x4 <= 0, x1 <= 1, which cycles with the lowest row
+        let A = [vec![0.5f64,0.5f64,1f64], vec![-5.5f64,-1.5f64,0f64], vec![-2.5f64,-0.5f64,0f64], vec![9f64,1f64,0f64], vec![1f64,0f64,0f64], vec![0f64,1f64,0f64], vec![0f64,0f64,1f64]];
+        let A: Vec<&[f64]> = A.iter().map(|col| col.as_slice()).collect();
+        let b = [0f64,0f64,1f64];
+        let c = [-10f64,57f64,9f64,24f64,0f64,0f64,0f64];
+        for solve_type in [SolveType::Standard, SolveType::Revised, SolveType::Dual].iter() {
+            let options = lexicographic.clone().objective_sense(ObjectiveSense::Minimize).solve_type(solve_type.clone());
+            let mut t = Tableau::new(&A, &b, &c, &options.clone().ratio_test_type(RatioTestType::LowestRow).detect_cycles(true)).unwrap();
+            assert_eq!(t.solve().unwrap().status, SolveStatus::CycleDetected, "Expected {:?} to cycle with the lowest row.", solve_type);
+            let mut t = Tableau::new(&A, &b, &c, &options).unwrap();
+            assert_eq!(t.solve().unwrap().objective, Fraction::from(-1), "Failed with {:?}.", solve_type);
+        }
+
+        // the same problem with x1 <= 1 as a bound, where the basic variables can also leave at their upper bound
+        let A = [vec![0.5f64,0.5f64], vec![-5.5f64,-1.5f64], vec![-2.5f64,-0.5f64], vec![9f64,1f64], vec![1f64,0f64], vec![0f64,1f64]];
+        let A: Vec<&[f64]> = A.iter().map(|col| col.as_slice()).collect();
+        let c = [10f64,-57f64,-9f64,-24f64,0f64,0f64];
+        for solve_type in [SolveType::Standard, SolveType::Revised].iter() {
+            let mut t = Tableau::with_bounds(&A, &[0f64,0f64], &c, &[0f64;6], &[1f64,inf,inf,inf,inf,inf], &lexicographic.clone().solve_type(solve_type.clone())).unwrap();
+            let solution = t.solve().unwrap();
+            assert_eq!(solution.objective, Fraction::from(1));
+            assert_eq!(solution.values[0], Fraction::from(1));
+        }
+
+        // with x1 = 1 as a row without a slack variable, so the rows are perturbed again once Phase 1 has finished
+        let A = [vec![0.5f64,0.5f64,1f64], vec![-5.5f64,-1.5f64,0f64], vec![-2.5f64,-0.5f64,0f64], vec![9f64,1f64,0f64], vec![1f64,0f64,0f64], vec![0f64,1f64,0f64]];
+        let A: Vec<&[f64]> = A.iter().map(|col| col.as_slice()).collect();
+        for big_M_solve_type in [BigMSolveType::TwoPhase, BigMSolveType::Detached].iter() {
+            for solve_type in [SolveType::Standard, SolveType::Revised, SolveType::Dual].iter() {
+                let options = lexicographic.clone().big_M_solve_type(big_M_solve_type.clone()).solve_type(solve_type.clone());
+                let mut t = Tableau::new(&A, &[0f64,0f64,1f64], &c, &options).unwrap();
+                assert_eq!(t.solve().unwrap().objective, Fraction::from(1), "Failed with {:?} and {:?}.", solve_type, big_M_solve_type);
+            }
+        }
+    }
+    #[test]
+    fn time_limit_and_cycle_detection(){
+        use std::time::Duration;
+
+        // max 10x1 - 57x2 - 9x3 - 24x4 s.t. 0.5x1 - 5.5x2 - 2.5x3 + 9x4 <= 0, 0.5x1 - 1.5x2 - 0.5x3 + x4 <= 0, x1 <= 1, which cycles with the most negative reduced cost
+        let A = [vec![0.5f64,0.5f64,1f64], vec![-5.5f64,-1.5f64,0f64], vec![-2.5f64,-0.5f64,0f64], vec![9f64,1f64,0f64], vec![1f64,0f64,0f64], vec![0f64,1f64,0f64], vec![0f64,0f64,1f64]];
+        let A: Vec<&[f64]> = A.iter().map(|col| col.as_slice()).collect();
+        let b = [0f64,0f64,1f64];
+        let c = [10f64,-57f64,-9f64,-24f64,0f64,0f64,0f64];
+        let options = SolverOptions::new().variable_select_type(VariableSelectType::Standard).detect_cycles(true);
+
+        // the cycle is 6 degenerate pivots long, so the starting basis is the first one to be visited again
+        for solve_type in [SolveType::Standard, SolveType::Revised].iter() {
+            let mut t = Tableau::new(&A, &b, &c, &options.clone().solve_type(solve_type.clone())).unwrap();
+            let solution = t.solve().unwrap();
+            assert_eq!(solution.status, SolveStatus::CycleDetected);
+            assert_eq!(solution.iterations, 6);
+            assert_eq!(solution.basis, vec![4, 5, 6]);
+            assert_eq!(solution.objective, Fraction::from(0));
+        }
+
+        // without cycle detection, which is the default, the solve only stops at the iteration limit
+        let mut t = Tableau::new(&A, &b, &c, &SolverOptions::new().variable_select_type(VariableSelectType::Standard).max_iterations(100)).unwrap();
+        let solution = t.solve().unwrap();
+        assert_eq!(solution.status, SolveStatus::IterationLimit);
+        assert_eq!(solution.iterations, 100);
+
+        // the same basis in a different order of rows is the same basis
+        let mut t = Tableau::new(&A, &b, &c, &options).unwrap();
+        let key = t.basis_key();
+        t.basis_indecies.reverse();
+        assert_eq!(t.basis_key(), key);
+        t.bounds[0].at_upper = true;
+        assert_ne!(t.basis_key(), key);
+
+        // a time limit of 0 stops before the first pivot
+        let mut t = Tableau::new(&A, &b, &c, &options.clone().detect_cycles(false).time_limit(Duration::from_secs(0))).unwrap();
+        let solution = t.solve().unwrap();
+        assert_eq!(solution.status, SolveStatus::TimeLimit);
+        assert_eq!(solution.iterations, 0);
+        assert_eq!(solution.values, vec![Fraction::from(0), Fraction::from(0), Fraction::from(0), Fraction::from(0), Fraction::from(0), Fraction::from(0), Fraction::from(1)]);
+
+        // bound flips do not change the basis, so they are not mistaken for a cycle
+        let A = [vec![1f64], vec![1f64], vec![1f64]];
+        let A: Vec<&[f64]> = A.iter().map(|col| col.as_slice()).collect();
+        let mut t = Tableau::with_bounds(&A, &[10f64], &[1f64,1f64,0f64], &[0f64,0f64,0f64], &[2f64,3f64,f64::INFINITY], &SolverOptions::new()).unwrap();
+        let solution = t.solve().unwrap();
+        assert_eq!(solution.status, SolveStatus::Optimal);
+        assert_eq!(solution.statistics.bound_flips, 2);
+        assert_eq!(solution.objective, Fraction::from(5));
+    }
+    #[test]
+    fn large_fractions(){
+        // a dense 12x12 problem with entries from 1 to 97, which needs numerators and denominators that do not fit in an i64 by the end
+        let n = 12;
+        let mut seed: u64 = 12345;
+        let mut next = || {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            ((seed >> 33) % 97 + 1) as f64
+        };
+        let mut A = Vec::new();
+        for _ in 0..n {
+            A.push((0..n).map(|_| next()).collect::<Vec<f64>>());
+        }
+        for j in 0..n {
+            let mut column = vec![0f64;n];
+            column[j] = 1f64;
+            A.push(column);
+        }
+        let b: Vec<f64> = (0..n).map(|_| next() * 10f64).collect();
+        let mut c: Vec<f64> = (0..n).map(|_| next()).collect();
+        c.extend(vec![0f64;n]);
+        let A: Vec<&[f64]> = A.iter().map(|col| col.as_slice()).collect();
+
+        let mut t = Tableau::new(&A, &b, &c, &SolverOptions::new()).unwrap();
+        let solution = t.solve().unwrap();
+        assert_eq!(solution.status, SolveStatus::Optimal);
+        assert_eq!(solution.objective, Fraction::new(456950, 1737));
+
+        // the solution is exactly feasible, and the duals give exactly the same objective value
+        let duals = solution.duals.unwrap();
+        let mut dual_objective = Fraction::from(0);
+        for row in 0..n {
+            let mut sum = Fraction::from(0);
+            for (column, value) in A.iter().zip(solution.values.iter()) {
+                sum = sum + Fraction::from(column[row]) * value.clone();
+            }
+            assert_eq!(sum, Fraction::from(b[row]));
+            dual_objective = dual_objective + duals[row].clone() * Fraction::from(b[row]);
+        }
+        assert_eq!(dual_objective, solution.objective);
+
+        // f64 takes the same pivots, and ends up within rounding error of the exact objective
+        let mut t = Tableau::<f64>::with_scalar(&A, &b, &c, &SolverOptions::new()).unwrap();
+        let rounded = t.solve().unwrap();
+        assert_eq!(rounded.status, SolveStatus::Optimal);
+        assert_eq!(rounded.basis, solution.basis);
+        assert!((rounded.objective - 456950f64/1737f64).abs() < 1e-9);
+    }
+    #[test]
+    fn scalar_types(){
+        use num_rational::BigRational;
+        use super::super::scalar::Scalar;
+        use super::super::pricing::{PricingRule, PricingContext};
+
+        // the same problems solved in f64 and BigRational give the same answers as in Fraction
+        fn compare<T: Scalar>(A: &[&[f64]], b: &[f64], c: &[f64], lower: &[f64], upper: &[f64], options: &SolverOptions) {
+            let exact = Tableau::with_bounds(A, b, c, lower, upper, options).unwrap().solve().unwrap();
+            let other = Tableau::<T>::with_scalar_bounds(A, b, c, lower, upper, options).unwrap().solve().unwrap();
+            assert_eq!(other.status, exact.status, "Failed matching the status with {:?}.", options);
+            assert!((other.objective.to_f64() - exact.objective.to_f64()).abs() < 1e-6, "Failed matching the objective with {:?}.", options);
+            for (value, expected) in other.values.iter().zip(exact.values.iter()) {
+                assert!((value.to_f64() - expected.to_f64()).abs() < 1e-6, "Failed matching the solution with {:?}.", options);
+            }
+        }
+        // max 3x + 5y s.t. x <= 4, 2y <= 12, 3x + 2y <= 18
+        let A = [vec![1f64,0f64,3f64], vec![0f64,2f64,2f64], vec![1f64,0f64,0f64], vec![0f64,1f64,0f64], vec![0f64,0f64,1f64]];
+        let A: Vec<&[f64]> = A.iter().map(|col| col.as_slice()).collect();
+        let (lower, upper) = (vec![0f64;5], vec![f64::INFINITY;5]);
+        for solve_type in [SolveType::Standard, SolveType::Revised, SolveType::Dual].iter() {
+            for variable_select_type in [VariableSelectType::Bland, VariableSelectType::Standard, VariableSelectType::SteepestEdge].iter() {
+                let options = SolverOptions::new().solve_type(solve_type.clone()).variable_select_type(variable_select_type.clone());
+                compare::<f64>(&A, &[4f64,12f64,18f64], &[3f64,5f64,0f64,0f64,0f64], &lower, &upper, &options);
+                compare::<BigRational>(&A, &[4f64,12f64,18f64], &[3f64,5f64,0f64,0f64,0f64], &lower, &upper, &options);
+            }
+        }
+        // x + y = 0.3, x - y >= 0.1 with x <= 0.25, which needs Phase 1 and has entries that are not exact in f64
+        let A = [vec![1f64,1f64], vec![1f64,-1f64], vec![0f64,-1f64]];
+        let A: Vec<&[f64]> = A.iter().map(|col| col.as_slice()).collect();
+        let (lower, upper) = (vec![0f64;3], vec![0.25f64, f64::INFINITY, f64::INFINITY]);
+        for big_M_solve_type in [BigMSolveType::TwoPhase, BigMSolveType::Detached].iter() {
+            let options = SolverOptions::new().big_M_solve_type(big_M_solve_type.clone());
+            compare::<f64>(&A, &[0.3f64,0.1f64], &[1f64,2f64,0f64], &lower, &upper, &options);
+            compare::<BigRational>(&A, &[0.3f64,0.1f64], &[1f64,2f64,0f64], &lower, &upper, &options);
+        }
+
+        // a custom rule runs on the Scalar it was written for, and is rejected by any other
+        struct FirstImproving;
+        impl PricingRule<f64> for FirstImproving {
+            fn choose(&self, context: &PricingContext<f64>) -> Option<usize> {
+                (0..context.number_of_variables()).find(|&col| context.direction(col) != 0)
+            }
+        }
+        let A = [vec![1f64,0f64,3f64], vec![0f64,2f64,2f64], vec![1f64,0f64,0f64], vec![0f64,1f64,0f64], vec![0f64,0f64,1f64]];
+        let A: Vec<&[f64]> = A.iter().map(|col| col.as_slice()).collect();
+        let options = SolverOptions::new().pricing_rule(FirstImproving);
+        let solution = Tableau::<f64>::with_scalar(&A, &[4f64,12f64,18f64], &[3f64,5f64,0f64,0f64,0f64], &options).unwrap().solve().unwrap();
+        assert_eq!(solution.objective, 36f64);
+        assert_eq!(Tableau::new(&A, &[4f64,12f64,18f64], &[3f64,5f64,0f64,0f64,0f64], &options).err(), Some(SimplexError::UnsupportedRule));
+    }
+    #[test]
+    fn tolerances(){
+        let solve = |A: &[Vec<f64>], b: &[f64], c: &[f64], options: &SolverOptions| {
+            let A: Vec<&[f64]> = A.iter().map(|col| col.as_slice()).collect();
+            Tableau::<f64>::with_scalar(&A, b, c, options).unwrap().solve().unwrap()
+        };
+
+        // max x s.t. 1e-12x <= 1e-13, x <= 5, where the first row is only rounding error and so is never pivoted on
+        let A = [vec![1e-12f64,1f64], vec![1f64,0f64], vec![0f64,1f64]];
+        let solution = solve(&A, &[1e-13f64,5f64], &[1f64,0f64,0f64], &SolverOptions::new());
+        assert_eq!(solution.status, SolveStatus::Optimal);
+        assert_eq!(solution.objective, 5f64);
+        let solution = solve(&A, &[1e-13f64,5f64], &[1f64,0f64,0f64], &SolverOptions::new().pivot_tolerance(0f64));
+        assert!((solution.objective - 0.1f64).abs() < 1e-9);
+
+        // max y + 1e-12x s.t. x <= 1, y <= 1, where the reduced cost of x is too small to enter the basis
+        let A = [vec![1f64,0f64], vec![0f64,1f64], vec![1f64,0f64], vec![0f64,1f64]];
+        let solution = solve(&A, &[1f64,1f64], &[1e-12f64,1f64,0f64,0f64], &SolverOptions::new().variable_select_type(VariableSelectType::Standard));
+        assert_eq!(solution.values[0], 0f64);
+        let solution = solve(&A, &[1f64,1f64], &[1e-12f64,1f64,0f64,0f64], &SolverOptions::new().variable_select_type(VariableSelectType::Standard).dual_feasibility_tolerance(0f64));
+        assert_eq!(solution.values[0], 1f64);
+
+        // x + y = 1 and x + y = 1 + 1e-7 are infeasible, unless Phase 1 accepts a violation of 1e-7
+        let A = [vec![1f64,1f64], vec![1f64,1f64]];
+        for big_M_solve_type in [BigMSolveType::TwoPhase, BigMSolveType::Detached].iter() {
+            let options = SolverOptions::new().big_M_solve_type(big_M_solve_type.clone());
+            assert_eq!(solve(&A, &[1f64,1.0000001f64], &[1f64,1f64], &options).status, SolveStatus::Infeasible);
+            let solution = solve(&A, &[1f64,1.0000001f64], &[1f64,1f64], &options.primal_feasibility_tolerance(1e-6));
+            assert_eq!(solution.status, SolveStatus::Optimal, "Failed with {:?}.", big_M_solve_type);
+            assert!((solution.objective - 1f64).abs() < 1e-6);
+        }
+
+        // the exact Scalars ignore the tolerances
+        let A = [vec![1f64,1f64], vec![1f64,1f64]];
+        let A: Vec<&[f64]> = A.iter().map(|col| col.as_slice()).collect();
+        let mut t = Tableau::new(&A, &[1f64,1.0000001f64], &[1f64,1f64], &SolverOptions::new().primal_feasibility_tolerance(1e-6)).unwrap();
+        assert_eq!(t.solve().unwrap().status, SolveStatus::Infeasible);
+
+        assert_eq!(Tableau::new(&A, &[1f64,1f64], &[1f64,1f64], &SolverOptions::new().pivot_tolerance(-1f64)).err(), Some(SimplexError::InvalidTolerance(-1f64)));
+        assert!(matches!(Tableau::new(&A, &[1f64,1f64], &[1f64,1f64], &SolverOptions::new().dual_feasibility_tolerance(f64::NAN)).err(), Some(SimplexError::InvalidTolerance(_))));
+    }
+    #[test]
+    fn rounding_in_big_M(){
+        // max 3x + 5y s.t. x <= 4, 2y <= 12, 3x + 2y <= 18, with rounding error left in the coefficient of M of the reduced costs
+        let A = [vec![1f64,0f64,3f64], vec![0f64,2f64,2f64], vec![1f64,0f64,0f64], vec![0f64,1f64,0f64], vec![0f64,0f64,1f64]];
+        let A: Vec<&[f64]> = A.iter().map(|col| col.as_slice()).collect();
+        for variable_select_type in [VariableSelectType::Bland, VariableSelectType::Standard, VariableSelectType::SteepestEdge, VariableSelectType::Devex].iter() {
+            let mut t = Tableau::<f64>::with_scalar(&A, &[4f64,12f64,18f64], &[3f64,5f64,0f64,0f64,0f64], &SolverOptions::new().variable_select_type(variable_select_type.clone())).unwrap();
+            t.reduced_cost[0] = BigM::new(-3f64, 1e-17);
+            t.reduced_cost[1] = BigM::new(-5f64, 1e-17);
+            t.compute_entering_variable();
+            assert!(!t.solved, "Failed with {:?}.", variable_select_type);
+            let expected = if *variable_select_type == VariableSelectType::Bland { 0 } else { 1 };
+            assert_eq!(t.entering_variable_index, expected, "Failed with {:?}.", variable_select_type);
+        }
+    }
 }